@@ -1,94 +1,69 @@
-//! This is a sample program for running a Rocket.rs server. This redirects
-//! users to Discord's authorization page, requesting the `identity` scope.
+//! This is a sample program for running a Rocket.rs server. This logs users in
+//! with Discord, requesting the `identify` scope, and greets them by name.
 //!
-//! Once they have authorized, it will take the `code` given and then exchange
-//! it for an access token, which can be used to access the user's identity.
+//! The login, callback, and logout routes are mounted under `/auth` by the
+//! `DiscordAuth` fairing, which exchanges the code given for an access token
+//! and keeps the logged in user in a sealed cookie.
 //!
-//! This example requires the following environment variables, both available
+//! This example requires the following environment variables, all available
 //! from your Discord application's settings:
 //!
 //! - `DISCORD_CLIENT_ID`
 //! - `DISCORD_CLIENT_SECRET`
+//! - `DISCORD_REDIRECT_URIS`
 //!
 //! You will also need to register a redirect URI. Running this locally would
-//! cause the redirect URI to be `http://localhost:8000` for example. This can
-//! be registered in your application's settings.
+//! cause the redirect URI to be `http://localhost:8000/auth/callback` for
+//! example. This can be registered in your application's settings.
 //!
 //! Example of how to run this:
 //!
 //! `$ git clone https://github.com/serenity-rs/oauth`
 //! `$ cd oauth`
-//! `$ DISCORD_CLIENT_SECRET=my_secret DISCORD_CLIENT_ID=my_client_id cargo run --example rocket`
+//! `$ DISCORD_CLIENT_SECRET=my_secret DISCORD_CLIENT_ID=my_client_id DISCORD_REDIRECT_URIS=http://localhost:8000/auth/callback cargo run --example rocket --features rocket`
 
-#![feature(custom_derive, plugin)]
-#![plugin(rocket_codegen)]
-
-extern crate hyper;
-extern crate hyper_native_tls;
-extern crate serenity_oauth;
-extern crate rocket;
+#[macro_use] extern crate rocket;
 
 use hyper::net::HttpsConnector;
 use hyper::Client as HyperClient;
 use hyper_native_tls::NativeTlsClient;
+use rocket::response::content::RawHtml;
 use rocket::response::Redirect;
-use serenity_oauth::model::AccessTokenExchangeRequest;
-use serenity_oauth::{DiscordOAuthHyperRequester, Scope};
-use std::env;
-use std::error::Error;
-
-#[derive(Debug, FromForm)]
-struct Params {
-    code: String,
-}
+use rocket::{Build, Rocket};
+use serenity_oauth::integrations::rocket::{DiscordAuth, DiscordUser};
+use serenity_oauth::seal::{SealingKey, SessionSealer};
 
-fn get_client_id() -> u64 {
-    env::var("DISCORD_CLIENT_ID")
-        .expect("No DISCORD_CLIENT_ID present")
-        .parse::<u64>()
-        .expect("Error parsing DISCORD_CLIENT_ID into u64")
+#[get("/")]
+fn get_index(user: DiscordUser) -> RawHtml<String> {
+    RawHtml(format!(
+        "Hello, {}! <form method=\"post\" action=\"/auth/logout\">\
+         <button>Log out</button></form>",
+        user.0.username,
+    ))
 }
 
-fn get_client_secret() -> String {
-    env::var("DISCORD_CLIENT_SECRET")
-        .expect("No DISCORD_CLIENT_SECRET present")
+#[get("/", rank = 2)]
+fn get_login() -> Redirect {
+    Redirect::to("/auth/login")
 }
 
-#[get("/callback?<params>")]
-fn get_callback(params: Params) -> Result<String, Box<Error>> {
-    // Exchange the code for an access token.
-    let ssl = NativeTlsClient::new()?;
-    let connector = HttpsConnector::new(ssl);
-    let client = HyperClient::with_connector(connector);
+#[launch]
+fn rocket() -> Rocket<Build> {
+    let oauth = serenity_oauth::config::from_env()
+        .expect("Error loading the configuration");
 
-    let response = client.exchange_code(&AccessTokenExchangeRequest::new(
-        get_client_id(),
-        get_client_secret(),
-        params.code,
-        "http://localhost:8000/callback",
-    ))?;
+    let ssl = NativeTlsClient::new().expect("Error creating TLS client");
+    let client = HyperClient::with_connector(HttpsConnector::new(ssl));
+    let sealer = SessionSealer::new(SealingKey::generate());
 
-    Ok(format!("The user's access token is: {}", response.access_token))
-}
-
-#[get("/")]
-fn get_redirect() -> Redirect {
-    // Although this example does not use a state, you _should always_ use one
-    // in production for security purposes.
-    let url = serenity_oauth::utils::authorization_code_grant_url(
-        get_client_id(),
-        &[Scope::Identify],
-        None,
-        "http://localhost:8000/callback",
-    );
-
-    Redirect::to(&url)
-}
+    // Cookies are sent over plain HTTP, as this runs on localhost.
+    let auth = DiscordAuth::new(oauth, client, sealer)
+        .with_secure_cookies(false);
 
-fn main() {
-    rocket::ignite()
+    rocket::build()
+        .attach(auth)
         .mount("/", routes![
-            get_callback,
-            get_redirect,
-        ]).launch();
+            get_index,
+            get_login,
+        ])
 }
@@ -1,16 +1,55 @@
 //! Bridged support for the `hyper` HTTP client.
 
-use hyper::client::{Body, Client as HyperClient};
-use hyper::header::ContentType;
+use hyper::client::{Body, Client as HyperClient, Response};
+use hyper::header::{Authorization, Basic, Bearer, ContentType, Headers};
+use hyper::method::Method;
+use hyper::status::StatusCode;
+use percent_encoding::{percent_encode, EncodeSet};
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
 use serde_json;
 use serde_urlencoded;
-use ::constants::BASE_TOKEN_URI;
 use ::model::{
     AccessTokenExchangeRequest,
     AccessTokenResponse,
+    AddGroupDmRecipientRequest,
+    AddGuildMemberRequest,
+    AddGuildMemberResponse,
+    ApiErrorResponse,
+    ApplicationRoleConnection,
+    ApplicationRoleConnectionMetadata,
+    AuthorizationInformation,
+    ClientCredentialsAccessTokenResponse,
+    ClientCredentialsRequest,
+    Connection,
+    CreateGroupDmRequest,
+    CurrentUser,
+    CurrentUserGuild,
+    CurrentUserGuildsQuery,
+    DiscoveryDocument,
+    EditWebhookMessageRequest,
+    ExecuteWebhookRequest,
+    Grant,
+    GroupDmChannel,
+    GuildMember,
+    Jwks,
     RefreshTokenRequest,
+    TokenErrorResponse,
+    WebhookCredentials,
+    WebhookMessage,
 };
-use ::Result;
+use ::store::TokenStore;
+use std::borrow::Borrow;
+use std::vec::IntoIter;
+use ::{Error, Result};
+use super::{ClientAuthMethod, RequestOptions};
+
+/// Discord's JSON error code for a request missing permissions.
+const MISSING_PERMISSIONS_CODE: u64 = 50013;
+/// Discord's JSON error code for an access token missing a required scope.
+const MISSING_SCOPE_CODE: u64 = 50026;
+/// The maximum number of guilds Discord returns per page.
+const GUILDS_PAGE_LIMIT: u8 = 200;
 
 /// A trait used that implements methods for interacting with Discord's OAuth2
 /// API on Hyper's client.
@@ -40,12 +79,44 @@ use ::Result;
 ///
 /// For examples of how to use the trait with the Client, refer to the trait's
 /// methods.
+///
+/// The trait is implemented on hyper's Client using the default
+/// [`RequestOptions`]. To configure how requests are made, wrap the Client in a
+/// [`HyperRequester`] instead.
+///
+/// [`HyperRequester`]: struct.HyperRequester.html
+/// [`RequestOptions`]: ../struct.RequestOptions.html
 pub trait DiscordOAuthHyperRequester {
-    /// Exchanges a code for the user's access token.
+    /// Adds the user that authorized the access token in the request to an
+    /// existing group DM, using your bot's token.
+    ///
+    /// The access token must have been granted the [`Scope::GdmJoin`] scope,
+    /// otherwise [`Error::MissingScope`] is returned.
+    ///
+    /// [`Error::MissingScope`]: ../../enum.Error.html#variant.MissingScope
+    /// [`Scope::GdmJoin`]: ../../enum.Scope.html#variant.GdmJoin
+    fn add_group_dm_recipient(
+        &self,
+        bot_token: &str,
+        channel_id: u64,
+        user_id: u64,
+        request: &AddGroupDmRecipientRequest,
+    ) -> Result<()>;
+
+    /// Adds the user that authorized the access token in the request to a
+    /// guild, using your bot's token.
+    ///
+    /// The access token must have been granted the [`Scope::GuildsJoin`]
+    /// scope, and your bot must be a member of the guild with the "Create
+    /// Instant Invite" permission. Setting optional fields in the request
+    /// requires additional permissions.
+    ///
+    /// If the bot is missing permissions, [`Error::MissingPermissions`] is
+    /// returned.
     ///
     /// # Examples
     ///
-    /// Exchange a code for an access token:
+    /// Add a user to a guild with a role:
     ///
     /// ```rust,no_run
     /// extern crate hyper;
@@ -55,20 +126,29 @@ pub trait DiscordOAuthHyperRequester {
     /// #
     /// # fn try_main() -> Result<(), Box<Error>> {
     /// use hyper::Client;
-    /// use serenity_oauth::model::AccessTokenExchangeRequest;
+    /// use serenity_oauth::model::{
+    ///     AddGuildMemberRequest,
+    ///     AddGuildMemberResponse,
+    /// };
     /// use serenity_oauth::DiscordOAuthHyperRequester;
     ///
-    /// let request_data = AccessTokenExchangeRequest::new(
-    ///     249608697955745802,
-    ///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
-    ///     "user code here",
-    ///     "https://myapplication.website",
-    /// );
+    /// let mut request = AddGuildMemberRequest::new("user access token");
+    /// request.roles.push(381880193700069377);
     ///
     /// let client = Client::new();
-    /// let response = client.exchange_code(&request_data)?;
+    /// let response = client.add_guild_member(
+    ///     "bot token",
+    ///     381880193251409931,
+    ///     114941315417899012,
+    ///     &request,
+    /// )?;
     ///
-    /// println!("Access token: {}", response.access_token);
+    /// match response {
+    ///     AddGuildMemberResponse::Added(member) => {
+    ///         println!("Added, with roles {:?}", member.roles);
+    ///     },
+    ///     AddGuildMemberResponse::AlreadyMember => println!("Already in"),
+    /// }
     /// #     Ok(())
     /// # }
     /// #
@@ -76,15 +156,26 @@ pub trait DiscordOAuthHyperRequester {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    fn exchange_code(&self, request: &AccessTokenExchangeRequest)
-        -> Result<AccessTokenResponse>;
+    ///
+    /// [`Error::MissingPermissions`]: ../../enum.Error.html#variant.MissingPermissions
+    /// [`Scope::GuildsJoin`]: ../../enum.Scope.html#variant.GuildsJoin
+    fn add_guild_member(
+        &self,
+        bot_token: &str,
+        guild_id: u64,
+        user_id: u64,
+        request: &AddGuildMemberRequest,
+    ) -> Result<AddGuildMemberResponse>;
 
-    /// Exchanges a refresh token, returning a new refresh token and fresh
-    /// access token.
+    /// Creates a group DM from the access tokens of its members, using your
+    /// bot's token.
+    ///
+    /// Each access token must have been granted the [`Scope::GdmJoin`] scope,
+    /// otherwise [`Error::MissingScope`] is returned.
     ///
     /// # Examples
     ///
-    /// Exchange a refresh token:
+    /// Create a group DM for two users:
     ///
     /// ```rust,no_run
     /// extern crate hyper;
@@ -94,20 +185,149 @@ pub trait DiscordOAuthHyperRequester {
     /// #
     /// # fn try_main() -> Result<(), Box<Error>> {
     /// use hyper::Client;
-    /// use serenity_oauth::model::RefreshTokenRequest;
+    /// use serenity_oauth::model::CreateGroupDmRequest;
     /// use serenity_oauth::DiscordOAuthHyperRequester;
     ///
-    /// let request_data = RefreshTokenRequest::new(
+    /// let request = CreateGroupDmRequest::new(vec![
+    ///     "first user access token".to_owned(),
+    ///     "second user access token".to_owned(),
+    /// ]);
+    ///
+    /// let client = Client::new();
+    /// let channel = client.create_group_dm("bot token", &request)?;
+    ///
+    /// println!("Created group DM {}", channel.id);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Error::MissingScope`]: ../../enum.Error.html#variant.MissingScope
+    /// [`Scope::GdmJoin`]: ../../enum.Scope.html#variant.GdmJoin
+    fn create_group_dm(&self, bot_token: &str, request: &CreateGroupDmRequest)
+        -> Result<GroupDmChannel>;
+
+    /// Retrieves information about the authorization of the given access
+    /// token, including the scopes it was granted and when it expires.
+    ///
+    /// The user that authorized the access token is only included if the
+    /// [`Scope::Identify`] scope was granted. To check whether an access token
+    /// is still valid, use [`validate_access_token`].
+    ///
+    /// # Examples
+    ///
+    /// Check which scopes were granted to an access token:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::{DiscordOAuthHyperRequester, Scope};
+    ///
+    /// let client = Client::new();
+    /// let info = client.current_authorization("user access token")?;
+    ///
+    /// println!("Expires at {}", info.expires);
+    ///
+    /// if !info.has_scope(&Scope::Guilds) {
+    ///     println!("The user must authorize the guilds scope");
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Scope::Identify`]: ../../enum.Scope.html#variant.Identify
+    /// [`validate_access_token`]: #method.validate_access_token
+    fn current_authorization(&self, access_token: &str)
+        -> Result<AuthorizationInformation>;
+
+    /// Retrieves information about the user that authorized the given access
+    /// token.
+    ///
+    /// This requires the [`Scope::Identify`] scope. The user's email address is
+    /// only included if the [`Scope::Email`] scope was granted.
+    ///
+    /// # Examples
+    ///
+    /// Exchange a code for an access token, and then retrieve the user:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::AccessTokenExchangeRequest;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let client = Client::new();
+    /// let response = client.exchange_code(&AccessTokenExchangeRequest::new(
     ///     249608697955745802,
     ///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
     ///     "user code here",
     ///     "https://myapplication.website",
-    /// );
+    /// ))?;
+    ///
+    /// let user = client.current_user(&response.access_token)?;
+    ///
+    /// println!("Logged in as {}", user.username);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Scope::Email`]: ../../enum.Scope.html#variant.Email
+    /// [`Scope::Identify`]: ../../enum.Scope.html#variant.Identify
+    fn current_user(&self, access_token: &str) -> Result<CurrentUser>;
+
+    /// Retrieves the third-party accounts linked to the user that authorized
+    /// the given access token.
+    ///
+    /// This requires the [`Scope::Connections`] scope.
+    ///
+    /// # Examples
+    ///
+    /// Check whether a user has linked a verified GitHub account:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::ConnectionType;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
     ///
     /// let client = Client::new();
-    /// let response = client.exchange_refresh_token(&request_data)?;
+    /// let connections = client.current_user_connections("user access token")?;
     ///
-    /// println!("Fresh access token: {}", response.access_token);
+    /// let github = connections.iter().find(|connection| {
+    ///     connection.kind == ConnectionType::GitHub
+    ///         && connection.verified
+    ///         && !connection.revoked
+    /// });
+    ///
+    /// if let Some(github) = github {
+    ///     println!("GitHub account: {}", github.name);
+    /// }
     /// #     Ok(())
     /// # }
     /// #
@@ -115,31 +335,1759 @@ pub trait DiscordOAuthHyperRequester {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    fn exchange_refresh_token(&self, request: &RefreshTokenRequest)
-        -> Result<AccessTokenResponse>;
-}
+    ///
+    /// [`Scope::Connections`]: ../../enum.Scope.html#variant.Connections
+    fn current_user_connections(&self, access_token: &str)
+        -> Result<Vec<Connection>>;
 
-impl DiscordOAuthHyperRequester for HyperClient {
-    fn exchange_code(&self, request: &AccessTokenExchangeRequest)
-        -> Result<AccessTokenResponse> {
-        let body = serde_urlencoded::to_string(request)?;
+    /// Retrieves a page of the guilds that the user who authorized the given
+    /// access token is a member of.
+    ///
+    /// This requires the [`Scope::Guilds`] scope. To walk through all of the
+    /// user's guilds, use [`current_user_guilds_iter`].
+    ///
+    /// # Examples
+    ///
+    /// Retrieve the first 10 guilds of a user, and print the ones they own:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::CurrentUserGuildsQuery;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let client = Client::new();
+    /// let guilds = client.current_user_guilds(
+    ///     "user access token",
+    ///     &CurrentUserGuildsQuery {
+    ///         limit: Some(10),
+    ///         ..Default::default()
+    ///     },
+    /// )?;
+    ///
+    /// for guild in guilds.iter().filter(|guild| guild.owner) {
+    ///     println!("Owner of {}", guild.name);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Scope::Guilds`]: ../../enum.Scope.html#variant.Guilds
+    /// [`current_user_guilds_iter`]: #method.current_user_guilds_iter
+    fn current_user_guilds(
+        &self,
+        access_token: &str,
+        query: &CurrentUserGuildsQuery,
+    ) -> Result<Vec<CurrentUserGuild>>;
 
-        let response = self.post(BASE_TOKEN_URI)
-            .header(ContentType::form_url_encoded())
-            .body(Body::BufBody(body.as_bytes(), body.len()))
-            .send()?;
+    /// Retrieves the member information of the user that authorized the given
+    /// access token in a guild.
+    ///
+    /// This requires the [`Scope::GuildsMembersRead`] scope, and does not
+    /// require your application to have a bot in the guild.
+    ///
+    /// # Examples
+    ///
+    /// Check whether a user has a role in your guild:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let client = Client::new();
+    /// let member = client.current_user_guild_member(
+    ///     "user access token",
+    ///     381880193251409931,
+    /// )?;
+    ///
+    /// if member.has_role(381880193700069377) {
+    ///     println!("The user is a supporter");
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Scope::GuildsMembersRead`]: ../../enum.Scope.html#variant.GuildsMembersRead
+    fn current_user_guild_member(&self, access_token: &str, guild_id: u64)
+        -> Result<GuildMember>;
 
-        serde_json::from_reader(response).map_err(From::from)
+    /// Creates an iterator over all of the guilds that the user who authorized
+    /// the given access token is a member of, retrieving pages as needed.
+    ///
+    /// Iteration stops after the first error.
+    ///
+    /// # Examples
+    ///
+    /// Find the guilds in which a user has the Administrator permission:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_model;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_model::Permissions;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let client = Client::new();
+    ///
+    /// for guild in client.current_user_guilds_iter("user access token") {
+    ///     let guild = guild?;
+    ///
+    ///     if guild.permissions.contains(Permissions::ADMINISTRATOR) {
+    ///         println!("Administrator of {}", guild.name);
+    ///     }
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    fn current_user_guilds_iter<'a>(&'a self, access_token: &'a str)
+        -> CurrentUserGuildsIter<'a, Self> where Self: Sized {
+        CurrentUserGuildsIter {
+            after: None,
+            done: false,
+            page: Vec::new().into_iter(),
+            access_token,
+            requester: self,
+        }
     }
 
-    fn exchange_refresh_token(&self, request: &RefreshTokenRequest)
-        -> Result<AccessTokenResponse> {
-        let body = serde_json::to_string(request)?;
+    /// Deletes a webhook.
+    ///
+    /// # Examples
+    ///
+    /// Delete a webhook that is no longer needed:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::WebhookCredentials;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let webhook = WebhookCredentials::new(
+    ///     381880193700069377,
+    ///     "webhook token",
+    /// );
+    ///
+    /// let client = Client::new();
+    /// client.delete_webhook(&webhook)?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    fn delete_webhook(&self, webhook: &WebhookCredentials) -> Result<()>;
 
-        let response = self.post(BASE_TOKEN_URI)
-            .body(Body::BufBody(body.as_bytes(), body.len()))
-            .send()?;
+    /// Deletes a message previously sent by a webhook.
+    fn delete_webhook_message(
+        &self,
+        webhook: &WebhookCredentials,
+        message_id: u64,
+    ) -> Result<()>;
 
-        serde_json::from_reader(response).map_err(From::from)
+    /// Retrieves an OpenID Connect discovery document, such as Discord's at
+    /// [`oidc::DISCOVERY_URL`].
+    ///
+    /// # Examples
+    ///
+    /// Retrieve Discord's discovery document and key set, and create a
+    /// validator for ID tokens:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::oidc::{self, IdTokenValidator};
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let client = Client::new();
+    /// let document = client.discovery_document(oidc::DISCOVERY_URL)?;
+    /// let jwks = client.jwks(&document.jwks_uri)?;
+    ///
+    /// let validator = IdTokenValidator::from_discovery(
+    ///     249608697955745802,
+    ///     &document,
+    ///     jwks,
+    /// );
+    ///
+    /// let claims = validator.validate("id token", Some("nonce"))?;
+    ///
+    /// println!("Authenticated user {}", claims.sub);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`oidc::DISCOVERY_URL`]: ../../oidc/constant.DISCOVERY_URL.html
+    fn discovery_document(&self, url: &str) -> Result<DiscoveryDocument>;
+
+    /// Edits a message previously sent by a webhook, returning the edited
+    /// message.
+    ///
+    /// # Examples
+    ///
+    /// Send a message and then edit its content:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::{
+    ///     EditWebhookMessageRequest,
+    ///     ExecuteWebhookRequest,
+    ///     WebhookCredentials,
+    /// };
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let webhook = WebhookCredentials::new(
+    ///     381880193700069377,
+    ///     "webhook token",
+    /// );
+    ///
+    /// let client = Client::new();
+    /// let message = client.execute_webhook_and_wait(
+    ///     &webhook,
+    ///     &ExecuteWebhookRequest::new("Deploying..."),
+    /// )?;
+    ///
+    /// let mut edit = EditWebhookMessageRequest::default();
+    /// edit.content = Some("Deployed!".to_owned());
+    ///
+    /// client.edit_webhook_message(&webhook, message.id, &edit)?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    fn edit_webhook_message(
+        &self,
+        webhook: &WebhookCredentials,
+        message_id: u64,
+        request: &EditWebhookMessageRequest,
+    ) -> Result<WebhookMessage>;
+
+    /// Sends a message through a webhook.
+    ///
+    /// To receive the sent message, use [`execute_webhook_and_wait`].
+    ///
+    /// # Examples
+    ///
+    /// Send a message through the webhook created by a
+    /// [`Scope::WebhookIncoming`] authorization:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::{ExecuteWebhookRequest, WebhookCredentials};
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let webhook = WebhookCredentials::new(
+    ///     381880193700069377,
+    ///     "webhook token",
+    /// );
+    ///
+    /// let client = Client::new();
+    /// client.execute_webhook(
+    ///     &webhook,
+    ///     &ExecuteWebhookRequest::new("Hello from the webhook!"),
+    /// )?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Scope::WebhookIncoming`]: ../../enum.Scope.html#variant.WebhookIncoming
+    /// [`execute_webhook_and_wait`]: #method.execute_webhook_and_wait
+    fn execute_webhook(
+        &self,
+        webhook: &WebhookCredentials,
+        request: &ExecuteWebhookRequest,
+    ) -> Result<()>;
+
+    /// Sends a message through a webhook, waiting for the message to be
+    /// created and returning it.
+    fn execute_webhook_and_wait(
+        &self,
+        webhook: &WebhookCredentials,
+        request: &ExecuteWebhookRequest,
+    ) -> Result<WebhookMessage>;
+
+    /// Exchanges a code for the user's access token.
+    ///
+    /// # Examples
+    ///
+    /// Exchange a code for an access token:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::AccessTokenExchangeRequest;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let request_data = AccessTokenExchangeRequest::new(
+    ///     249608697955745802,
+    ///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+    ///     "user code here",
+    ///     "https://myapplication.website",
+    /// );
+    ///
+    /// let client = Client::new();
+    /// let response = client.exchange_code(&request_data)?;
+    ///
+    /// println!("Access token: {}", response.access_token);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    fn exchange_code(&self, request: &AccessTokenExchangeRequest)
+        -> Result<AccessTokenResponse>;
+
+    /// Exchanges a code for the user's access token, along with the guild the
+    /// application's bot was added to and the webhook that was created, if
+    /// any.
+    ///
+    /// This works for any authorization flow, such as an extended
+    /// [`Scope::Bot`] authorization or a [`Scope::WebhookIncoming`]
+    /// authorization.
+    ///
+    /// # Examples
+    ///
+    /// Exchange the code of a bot authorization, setting the permissions
+    /// passed in the redirect:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::AccessTokenExchangeRequest;
+    /// use serenity_oauth::utils::Permissions;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let request_data = AccessTokenExchangeRequest::new(
+    ///     249608697955745802,
+    ///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+    ///     "user code here",
+    ///     "https://myapplication.website",
+    /// );
+    ///
+    /// # let permissions_param = "2048";
+    /// let client = Client::new();
+    /// let mut grant = client.exchange_grant(&request_data)?;
+    ///
+    /// // The `permissions` query parameter of the redirect.
+    /// grant.permissions = permissions_param.parse()
+    ///     .ok()
+    ///     .map(Permissions::from_bits_truncate);
+    ///
+    /// if let Some(ref guild) = grant.guild {
+    ///     println!("Added to {}", guild.name);
+    /// }
+    ///
+    /// if let Some(ref webhook) = grant.webhook {
+    ///     println!("Created webhook {}", webhook.id);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Scope::Bot`]: ../../enum.Scope.html#variant.Bot
+    /// [`Scope::WebhookIncoming`]: ../../enum.Scope.html#variant.WebhookIncoming
+    fn exchange_grant(&self, request: &AccessTokenExchangeRequest)
+        -> Result<Grant>;
+
+    /// Retrieves an access token for the owner of the application using the
+    /// client credentials grant.
+    ///
+    /// To avoid requesting a new token on every call, consider using a
+    /// [`ClientCredentialsCache`].
+    ///
+    /// # Examples
+    ///
+    /// Retrieve an access token with the [`Scope::Identify`] scope:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::ClientCredentialsRequest;
+    /// use serenity_oauth::{DiscordOAuthHyperRequester, Scope};
+    ///
+    /// let request_data = ClientCredentialsRequest::new(
+    ///     249608697955745802,
+    ///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+    ///     &[Scope::Identify],
+    /// );
+    ///
+    /// let client = Client::new();
+    /// let response = client.exchange_client_credentials(&request_data)?;
+    ///
+    /// println!("Access token: {}", response.access_token);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`ClientCredentialsCache`]: ../../cache/struct.ClientCredentialsCache.html
+    /// [`Scope::Identify`]: ../../enum.Scope.html#variant.Identify
+    fn exchange_client_credentials(&self, request: &ClientCredentialsRequest)
+        -> Result<ClientCredentialsAccessTokenResponse>;
+
+    /// Exchanges a refresh token, returning a new refresh token and fresh
+    /// access token.
+    ///
+    /// # Examples
+    ///
+    /// Exchange a refresh token:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::RefreshTokenRequest;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let request_data = RefreshTokenRequest::new(
+    ///     249608697955745802,
+    ///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+    ///     "user code here",
+    ///     "https://myapplication.website",
+    /// );
+    ///
+    /// let client = Client::new();
+    /// let response = client.exchange_refresh_token(&request_data)?;
+    ///
+    /// println!("Fresh access token: {}", response.access_token);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    fn exchange_refresh_token(&self, request: &RefreshTokenRequest)
+        -> Result<AccessTokenResponse>;
+
+    /// Retrieves a JSON Web Key Set, such as the one referenced by the
+    /// [`jwks_uri`] of a discovery document.
+    ///
+    /// [`jwks_uri`]: ../../model/struct.DiscoveryDocument.html#structfield.jwks_uri
+    fn jwks(&self, url: &str) -> Result<Jwks>;
+
+    /// Replaces your application's role connection metadata records, using
+    /// your bot's token.
+    ///
+    /// An application can have up to 5 records. The records in effect after
+    /// the update are returned.
+    ///
+    /// # Examples
+    ///
+    /// Register a record requiring users to have played a number of games:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::{
+    ///     ApplicationRoleConnectionMetadata,
+    ///     RoleConnectionMetadataType,
+    /// };
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let records = [ApplicationRoleConnectionMetadata::new(
+    ///     RoleConnectionMetadataType::IntegerGreaterThanOrEqual,
+    ///     "games_played",
+    ///     "Games Played",
+    ///     "Minimum number of games played",
+    /// )];
+    ///
+    /// let client = Client::new();
+    /// client.update_role_connection_metadata(
+    ///     "bot token",
+    ///     249608697955745802,
+    ///     &records,
+    /// )?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    fn update_role_connection_metadata(
+        &self,
+        bot_token: &str,
+        application_id: u64,
+        records: &[ApplicationRoleConnectionMetadata],
+    ) -> Result<Vec<ApplicationRoleConnectionMetadata>>;
+
+    /// Updates the role connection of the user that authorized the given
+    /// access token for your application.
+    ///
+    /// This requires the [`Scope::RoleConnectionsWrite`] scope.
+    ///
+    /// # Examples
+    ///
+    /// Update a user's number of games played:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::ApplicationRoleConnection;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let mut connection = ApplicationRoleConnection::default();
+    /// connection.platform_username = Some("nelly".to_owned());
+    /// connection.set_integer("games_played", 42);
+    ///
+    /// let client = Client::new();
+    /// client.update_current_user_role_connection(
+    ///     "user access token",
+    ///     249608697955745802,
+    ///     &connection,
+    /// )?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Scope::RoleConnectionsWrite`]: ../../enum.Scope.html#variant.RoleConnectionsWrite
+    fn update_current_user_role_connection(
+        &self,
+        access_token: &str,
+        application_id: u64,
+        connection: &ApplicationRoleConnection,
+    ) -> Result<ApplicationRoleConnection>;
+
+    /// Exchanges the refresh token of a grant kept in a [`TokenStore`],
+    /// storing the fresh grant under the same key on success.
+    ///
+    /// If the token endpoint reports that the grant has permanently been
+    /// invalidated - such as when the user deauthorized the application - the
+    /// stored grant is marked as revoked and `on_deauthorized` is called with
+    /// the key and the error returned by Discord. The error is then returned.
+    ///
+    /// Transient failures, such as network errors, leave the stored grant
+    /// untouched. Refer to [`Error::is_transient`] to decide whether to retry.
+    ///
+    /// # Examples
+    ///
+    /// Refresh a user's stored grant, unlinking their account if they have
+    /// deauthorized the application:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::model::RefreshTokenRequest;
+    /// use serenity_oauth::store::{MemoryTokenStore, TokenStore};
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let store = MemoryTokenStore::new();
+    /// let stored = store.get("114941315417899012").unwrap();
+    ///
+    /// let request_data = RefreshTokenRequest::new(
+    ///     249608697955745802,
+    ///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+    ///     "https://myapplication.website",
+    ///     stored.refresh_token,
+    /// );
+    ///
+    /// let client = Client::new();
+    /// let result = client.refresh_stored_token(
+    ///     &store,
+    ///     "114941315417899012",
+    ///     &request_data,
+    ///     |user_id, _| println!("User {} deauthorized us", user_id),
+    /// );
+    ///
+    /// match result {
+    ///     Ok(response) => println!("Refreshed: {}", response.access_token),
+    ///     Err(ref why) if why.is_revoked() => {},
+    ///     Err(why) => return Err(Box::new(why)),
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Error::is_transient`]: ../../enum.Error.html#method.is_transient
+    /// [`TokenStore`]: ../../store/trait.TokenStore.html
+    fn refresh_stored_token<S, F>(
+        &self,
+        store: &S,
+        key: &str,
+        request: &RefreshTokenRequest,
+        on_deauthorized: F,
+    ) -> Result<AccessTokenResponse>
+        where S: TokenStore, F: FnOnce(&str, &TokenErrorResponse) {
+        match self.exchange_refresh_token(request) {
+            Ok(response) => {
+                store.insert(key, response.clone().into());
+
+                Ok(response)
+            },
+            Err(Error::TokenRequest(ref inner)) if inner.is_revoked() => {
+                store.mark_revoked(key);
+                on_deauthorized(key, inner);
+
+                Err(Error::TokenRequest(inner.clone()))
+            },
+            Err(why) => Err(why),
+        }
+    }
+
+    /// Checks whether the given access token is still valid, returning
+    /// information about its authorization if it is.
+    ///
+    /// `None` is returned if the access token has expired or been revoked,
+    /// rather than an error. This is useful for checking a token taken from a
+    /// store before performing expensive work with it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::DiscordOAuthHyperRequester;
+    ///
+    /// let client = Client::new();
+    ///
+    /// match client.validate_access_token("user access token")? {
+    ///     Some(info) => println!("Valid until {}", info.expires),
+    ///     None => println!("The access token must be refreshed"),
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    fn validate_access_token(&self, access_token: &str)
+        -> Result<Option<AuthorizationInformation>> {
+        match self.current_authorization(access_token) {
+            Ok(info) => Ok(Some(info)),
+            Err(ref why) if why.is_unauthorized() => Ok(None),
+            Err(why) => Err(why),
+        }
+    }
+}
+
+impl DiscordOAuthHyperRequester for HyperClient {
+    fn add_group_dm_recipient(
+        &self,
+        bot_token: &str,
+        channel_id: u64,
+        user_id: u64,
+        request: &AddGroupDmRecipientRequest,
+    ) -> Result<()> {
+        add_group_dm_recipient(
+            self,
+            &RequestOptions::default(),
+            bot_token,
+            channel_id,
+            user_id,
+            request,
+        )
+    }
+
+    fn add_guild_member(
+        &self,
+        bot_token: &str,
+        guild_id: u64,
+        user_id: u64,
+        request: &AddGuildMemberRequest,
+    ) -> Result<AddGuildMemberResponse> {
+        let options = RequestOptions::default();
+
+        add_guild_member(self, &options, bot_token, guild_id, user_id, request)
+    }
+
+    fn create_group_dm(&self, bot_token: &str, request: &CreateGroupDmRequest)
+        -> Result<GroupDmChannel> {
+        create_group_dm(self, &RequestOptions::default(), bot_token, request)
+    }
+
+    fn current_authorization(&self, access_token: &str)
+        -> Result<AuthorizationInformation> {
+        current_authorization(self, &RequestOptions::default(), access_token)
+    }
+
+    fn current_user(&self, access_token: &str) -> Result<CurrentUser> {
+        current_user(self, &RequestOptions::default(), access_token)
+    }
+
+    fn current_user_connections(&self, access_token: &str)
+        -> Result<Vec<Connection>> {
+        let options = RequestOptions::default();
+
+        current_user_connections(self, &options, access_token)
+    }
+
+    fn current_user_guild_member(&self, access_token: &str, guild_id: u64)
+        -> Result<GuildMember> {
+        let options = RequestOptions::default();
+
+        current_user_guild_member(self, &options, access_token, guild_id)
+    }
+
+    fn current_user_guilds(
+        &self,
+        access_token: &str,
+        query: &CurrentUserGuildsQuery,
+    ) -> Result<Vec<CurrentUserGuild>> {
+        let options = RequestOptions::default();
+
+        current_user_guilds(self, &options, access_token, query)
+    }
+
+    fn delete_webhook(&self, webhook: &WebhookCredentials) -> Result<()> {
+        delete_webhook(self, &RequestOptions::default(), webhook)
+    }
+
+    fn delete_webhook_message(
+        &self,
+        webhook: &WebhookCredentials,
+        message_id: u64,
+    ) -> Result<()> {
+        delete_webhook_message(
+            self,
+            &RequestOptions::default(),
+            webhook,
+            message_id,
+        )
+    }
+
+    fn discovery_document(&self, url: &str) -> Result<DiscoveryDocument> {
+        get_json(self, url)
+    }
+
+    fn edit_webhook_message(
+        &self,
+        webhook: &WebhookCredentials,
+        message_id: u64,
+        request: &EditWebhookMessageRequest,
+    ) -> Result<WebhookMessage> {
+        edit_webhook_message(
+            self,
+            &RequestOptions::default(),
+            webhook,
+            message_id,
+            request,
+        )
+    }
+
+    fn execute_webhook(
+        &self,
+        webhook: &WebhookCredentials,
+        request: &ExecuteWebhookRequest,
+    ) -> Result<()> {
+        execute_webhook(
+            self,
+            &RequestOptions::default(),
+            webhook,
+            request,
+            false,
+        ).map(|_| ())
+    }
+
+    fn execute_webhook_and_wait(
+        &self,
+        webhook: &WebhookCredentials,
+        request: &ExecuteWebhookRequest,
+    ) -> Result<WebhookMessage> {
+        let response = execute_webhook(
+            self,
+            &RequestOptions::default(),
+            webhook,
+            request,
+            true,
+        )?;
+
+        serde_json::from_reader(response).map_err(From::from)
+    }
+
+    fn exchange_code(&self, request: &AccessTokenExchangeRequest)
+        -> Result<AccessTokenResponse> {
+        post_token(self, &RequestOptions::default(), request)
+    }
+
+    fn exchange_grant(&self, request: &AccessTokenExchangeRequest)
+        -> Result<Grant> {
+        post_token(self, &RequestOptions::default(), request)
+    }
+
+    fn exchange_client_credentials(&self, request: &ClientCredentialsRequest)
+        -> Result<ClientCredentialsAccessTokenResponse> {
+        post_token(self, &RequestOptions::default(), request)
+    }
+
+    fn exchange_refresh_token(&self, request: &RefreshTokenRequest)
+        -> Result<AccessTokenResponse> {
+        post_token(self, &RequestOptions::default(), request)
+    }
+
+    fn jwks(&self, url: &str) -> Result<Jwks> {
+        get_json(self, url)
+    }
+
+    fn update_role_connection_metadata(
+        &self,
+        bot_token: &str,
+        application_id: u64,
+        records: &[ApplicationRoleConnectionMetadata],
+    ) -> Result<Vec<ApplicationRoleConnectionMetadata>> {
+        update_role_connection_metadata(
+            self,
+            &RequestOptions::default(),
+            bot_token,
+            application_id,
+            records,
+        )
+    }
+
+    fn update_current_user_role_connection(
+        &self,
+        access_token: &str,
+        application_id: u64,
+        connection: &ApplicationRoleConnection,
+    ) -> Result<ApplicationRoleConnection> {
+        update_current_user_role_connection(
+            self,
+            &RequestOptions::default(),
+            access_token,
+            application_id,
+            connection,
+        )
+    }
+}
+
+/// A wrapper around hyper's Client that makes requests according to the given
+/// [`RequestOptions`].
+///
+/// # Examples
+///
+/// Create a requester that authenticates to the token endpoint using HTTP Basic
+/// authentication, keeping the client secret out of request bodies:
+///
+/// ```rust,no_run
+/// extern crate hyper;
+/// extern crate serenity_oauth;
+///
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// use hyper::Client;
+/// use serenity_oauth::bridge::hyper::HyperRequester;
+/// use serenity_oauth::bridge::{ClientAuthMethod, RequestOptions};
+/// use serenity_oauth::model::AccessTokenExchangeRequest;
+/// use serenity_oauth::DiscordOAuthHyperRequester;
+///
+/// let requester = HyperRequester::new(Client::new(), RequestOptions {
+///     client_auth: ClientAuthMethod::Basic,
+///     ..Default::default()
+/// });
+///
+/// let response = requester.exchange_code(&AccessTokenExchangeRequest::new(
+///     249608697955745802,
+///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+///     "user code here",
+///     "https://myapplication.website",
+/// ))?;
+///
+/// println!("Access token: {}", response.access_token);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// The Client can also be borrowed, such as to make a single request with
+/// different options than the other requests made with it.
+///
+/// [`RequestOptions`]: ../struct.RequestOptions.html
+pub struct HyperRequester<C = HyperClient> {
+    /// The wrapped hyper Client.
+    pub client: C,
+    /// The options to make requests with.
+    pub options: RequestOptions,
+}
+
+impl<C> HyperRequester<C> where C: Borrow<HyperClient> {
+    /// Wraps a hyper Client, making requests with the given options.
+    pub fn new(client: C, options: RequestOptions) -> Self {
+        Self {
+            client,
+            options,
+        }
+    }
+}
+
+impl<C> DiscordOAuthHyperRequester for HyperRequester<C>
+    where C: Borrow<HyperClient> {
+    fn add_group_dm_recipient(
+        &self,
+        bot_token: &str,
+        channel_id: u64,
+        user_id: u64,
+        request: &AddGroupDmRecipientRequest,
+    ) -> Result<()> {
+        add_group_dm_recipient(
+            self.client.borrow(),
+            &self.options,
+            bot_token,
+            channel_id,
+            user_id,
+            request,
+        )
+    }
+
+    fn add_guild_member(
+        &self,
+        bot_token: &str,
+        guild_id: u64,
+        user_id: u64,
+        request: &AddGuildMemberRequest,
+    ) -> Result<AddGuildMemberResponse> {
+        add_guild_member(
+            self.client.borrow(),
+            &self.options,
+            bot_token,
+            guild_id,
+            user_id,
+            request,
+        )
+    }
+
+    fn create_group_dm(&self, bot_token: &str, request: &CreateGroupDmRequest)
+        -> Result<GroupDmChannel> {
+        create_group_dm(self.client.borrow(), &self.options, bot_token, request)
+    }
+
+    fn current_authorization(&self, access_token: &str)
+        -> Result<AuthorizationInformation> {
+        current_authorization(self.client.borrow(), &self.options, access_token)
+    }
+
+    fn current_user(&self, access_token: &str) -> Result<CurrentUser> {
+        current_user(self.client.borrow(), &self.options, access_token)
+    }
+
+    fn current_user_connections(&self, access_token: &str)
+        -> Result<Vec<Connection>> {
+        current_user_connections(
+            self.client.borrow(),
+            &self.options,
+            access_token,
+        )
+    }
+
+    fn current_user_guild_member(&self, access_token: &str, guild_id: u64)
+        -> Result<GuildMember> {
+        current_user_guild_member(
+            self.client.borrow(),
+            &self.options,
+            access_token,
+            guild_id,
+        )
+    }
+
+    fn current_user_guilds(
+        &self,
+        access_token: &str,
+        query: &CurrentUserGuildsQuery,
+    ) -> Result<Vec<CurrentUserGuild>> {
+        current_user_guilds(
+            self.client.borrow(),
+            &self.options,
+            access_token,
+            query,
+        )
+    }
+
+    fn delete_webhook(&self, webhook: &WebhookCredentials) -> Result<()> {
+        delete_webhook(self.client.borrow(), &self.options, webhook)
+    }
+
+    fn delete_webhook_message(
+        &self,
+        webhook: &WebhookCredentials,
+        message_id: u64,
+    ) -> Result<()> {
+        delete_webhook_message(
+            self.client.borrow(),
+            &self.options,
+            webhook,
+            message_id,
+        )
+    }
+
+    fn discovery_document(&self, url: &str) -> Result<DiscoveryDocument> {
+        get_json(self.client.borrow(), url)
+    }
+
+    fn edit_webhook_message(
+        &self,
+        webhook: &WebhookCredentials,
+        message_id: u64,
+        request: &EditWebhookMessageRequest,
+    ) -> Result<WebhookMessage> {
+        edit_webhook_message(
+            self.client.borrow(),
+            &self.options,
+            webhook,
+            message_id,
+            request,
+        )
+    }
+
+    fn execute_webhook(
+        &self,
+        webhook: &WebhookCredentials,
+        request: &ExecuteWebhookRequest,
+    ) -> Result<()> {
+        execute_webhook(
+            self.client.borrow(),
+            &self.options,
+            webhook,
+            request,
+            false,
+        ).map(|_| ())
+    }
+
+    fn execute_webhook_and_wait(
+        &self,
+        webhook: &WebhookCredentials,
+        request: &ExecuteWebhookRequest,
+    ) -> Result<WebhookMessage> {
+        let response = execute_webhook(
+            self.client.borrow(),
+            &self.options,
+            webhook,
+            request,
+            true,
+        )?;
+
+        serde_json::from_reader(response).map_err(From::from)
+    }
+
+    fn exchange_code(&self, request: &AccessTokenExchangeRequest)
+        -> Result<AccessTokenResponse> {
+        post_token(self.client.borrow(), &self.options, request)
+    }
+
+    fn exchange_grant(&self, request: &AccessTokenExchangeRequest)
+        -> Result<Grant> {
+        post_token(self.client.borrow(), &self.options, request)
+    }
+
+    fn exchange_client_credentials(&self, request: &ClientCredentialsRequest)
+        -> Result<ClientCredentialsAccessTokenResponse> {
+        post_token(self.client.borrow(), &self.options, request)
+    }
+
+    fn exchange_refresh_token(&self, request: &RefreshTokenRequest)
+        -> Result<AccessTokenResponse> {
+        post_token(self.client.borrow(), &self.options, request)
+    }
+
+    fn jwks(&self, url: &str) -> Result<Jwks> {
+        get_json(self.client.borrow(), url)
+    }
+
+    fn update_role_connection_metadata(
+        &self,
+        bot_token: &str,
+        application_id: u64,
+        records: &[ApplicationRoleConnectionMetadata],
+    ) -> Result<Vec<ApplicationRoleConnectionMetadata>> {
+        update_role_connection_metadata(
+            self.client.borrow(),
+            &self.options,
+            bot_token,
+            application_id,
+            records,
+        )
+    }
+
+    fn update_current_user_role_connection(
+        &self,
+        access_token: &str,
+        application_id: u64,
+        connection: &ApplicationRoleConnection,
+    ) -> Result<ApplicationRoleConnection> {
+        update_current_user_role_connection(
+            self.client.borrow(),
+            &self.options,
+            access_token,
+            application_id,
+            connection,
+        )
+    }
+}
+
+/// An iterator over all of the guilds of the user who authorized an access
+/// token.
+///
+/// This is created by [`DiscordOAuthHyperRequester::current_user_guilds_iter`].
+///
+/// [`DiscordOAuthHyperRequester::current_user_guilds_iter`]: trait.DiscordOAuthHyperRequester.html#method.current_user_guilds_iter
+pub struct CurrentUserGuildsIter<'a, R: 'a> {
+    access_token: &'a str,
+    after: Option<u64>,
+    done: bool,
+    page: IntoIter<CurrentUserGuild>,
+    requester: &'a R,
+}
+
+impl<'a, R> Iterator for CurrentUserGuildsIter<'a, R>
+    where R: DiscordOAuthHyperRequester + 'a {
+    type Item = Result<CurrentUserGuild>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(guild) = self.page.next() {
+            self.after = Some(guild.id);
+
+            return Some(Ok(guild));
+        }
+
+        if self.done {
+            return None;
+        }
+
+        let query = CurrentUserGuildsQuery {
+            after: self.after,
+            before: None,
+            limit: Some(GUILDS_PAGE_LIMIT),
+        };
+
+        match self.requester.current_user_guilds(self.access_token, &query) {
+            Ok(page) => {
+                self.done = page.len() < GUILDS_PAGE_LIMIT as usize;
+                self.page = page.into_iter();
+
+                self.page.next().map(|guild| {
+                    self.after = Some(guild.id);
+
+                    Ok(guild)
+                })
+            },
+            Err(why) => {
+                self.done = true;
+
+                Some(Err(why))
+            },
+        }
+    }
+}
+
+/// How a request to Discord's API is authorized.
+enum ApiAuth<'a> {
+    /// Authorized by a user's access token.
+    Bearer(&'a str),
+    /// Authorized by your bot's token.
+    Bot(&'a str),
+    /// Not authorized by a header, such as for requests authorized by a
+    /// webhook token in the path.
+    None,
+}
+
+/// Sends a request to Discord's API, returning the response if its status code
+/// indicates success.
+fn api_request(
+    client: &HyperClient,
+    options: &RequestOptions,
+    method: Method,
+    path: &str,
+    auth: ApiAuth,
+    body: Option<String>,
+) -> Result<Response> {
+    let mut headers = Headers::new();
+    // Only a bot's token can be missing permissions, whereas the same error
+    // for an access token means the user is.
+    let is_bot = match auth {
+        ApiAuth::Bot(_) => true,
+        _ => false,
+    };
+
+    match auth {
+        ApiAuth::Bearer(token) => headers.set(Authorization(Bearer {
+            token: token.to_owned(),
+        })),
+        ApiAuth::Bot(token) => {
+            headers.set(Authorization(format!("Bot {}", token)));
+        },
+        ApiAuth::None => {},
+    }
+
+    let url = options.endpoints.url(path);
+    let mut request = client.request(method, &url);
+
+    if let Some(ref body) = body {
+        headers.set(ContentType::json());
+        request = request.body(Body::BufBody(body.as_bytes(), body.len()));
+    }
+
+    let response = request.headers(headers).send()?;
+
+    if response.status.is_success() {
+        return Ok(response);
+    }
+
+    let status = response.status;
+
+    match serde_json::from_reader::<_, ApiErrorResponse>(response) {
+        Ok(ref error) if is_bot && error.code == MISSING_PERMISSIONS_CODE => {
+            Err(Error::MissingPermissions(error.clone()))
+        },
+        Ok(ref error) if error.code == MISSING_SCOPE_CODE => {
+            Err(Error::MissingScope(error.clone()))
+        },
+        Ok(error) => Err(Error::Api(status, error)),
+        Err(_) => Err(Error::Status(status)),
+    }
+}
+
+fn add_group_dm_recipient(
+    client: &HyperClient,
+    options: &RequestOptions,
+    bot_token: &str,
+    channel_id: u64,
+    user_id: u64,
+    request: &AddGroupDmRecipientRequest,
+) -> Result<()> {
+    api_request(
+        client,
+        options,
+        Method::Put,
+        &format!("/channels/{}/recipients/{}", channel_id, user_id),
+        ApiAuth::Bot(bot_token),
+        Some(serde_json::to_string(request)?),
+    ).map(|_| ())
+}
+
+fn add_guild_member(
+    client: &HyperClient,
+    options: &RequestOptions,
+    bot_token: &str,
+    guild_id: u64,
+    user_id: u64,
+    request: &AddGuildMemberRequest,
+) -> Result<AddGuildMemberResponse> {
+    let response = api_request(
+        client,
+        options,
+        Method::Put,
+        &format!("/guilds/{}/members/{}", guild_id, user_id),
+        ApiAuth::Bot(bot_token),
+        Some(serde_json::to_string(request)?),
+    )?;
+
+    if response.status == StatusCode::NoContent {
+        return Ok(AddGuildMemberResponse::AlreadyMember);
+    }
+
+    serde_json::from_reader(response)
+        .map(AddGuildMemberResponse::Added)
+        .map_err(From::from)
+}
+
+fn create_group_dm(
+    client: &HyperClient,
+    options: &RequestOptions,
+    bot_token: &str,
+    request: &CreateGroupDmRequest,
+) -> Result<GroupDmChannel> {
+    let response = api_request(
+        client,
+        options,
+        Method::Post,
+        "/users/@me/channels",
+        ApiAuth::Bot(bot_token),
+        Some(serde_json::to_string(request)?),
+    )?;
+
+    serde_json::from_reader(response).map_err(From::from)
+}
+
+fn current_authorization(
+    client: &HyperClient,
+    options: &RequestOptions,
+    access_token: &str,
+) -> Result<AuthorizationInformation> {
+    let response = api_request(
+        client,
+        options,
+        Method::Get,
+        "/oauth2/@me",
+        ApiAuth::Bearer(access_token),
+        None,
+    )?;
+
+    serde_json::from_reader(response).map_err(From::from)
+}
+
+fn current_user(
+    client: &HyperClient,
+    options: &RequestOptions,
+    access_token: &str,
+) -> Result<CurrentUser> {
+    let response = api_request(
+        client,
+        options,
+        Method::Get,
+        "/users/@me",
+        ApiAuth::Bearer(access_token),
+        None,
+    )?;
+
+    serde_json::from_reader(response).map_err(From::from)
+}
+
+fn current_user_connections(
+    client: &HyperClient,
+    options: &RequestOptions,
+    access_token: &str,
+) -> Result<Vec<Connection>> {
+    let response = api_request(
+        client,
+        options,
+        Method::Get,
+        "/users/@me/connections",
+        ApiAuth::Bearer(access_token),
+        None,
+    )?;
+
+    serde_json::from_reader(response).map_err(From::from)
+}
+
+fn current_user_guild_member(
+    client: &HyperClient,
+    options: &RequestOptions,
+    access_token: &str,
+    guild_id: u64,
+) -> Result<GuildMember> {
+    let response = api_request(
+        client,
+        options,
+        Method::Get,
+        &format!("/users/@me/guilds/{}/member", guild_id),
+        ApiAuth::Bearer(access_token),
+        None,
+    )?;
+
+    serde_json::from_reader(response).map_err(From::from)
+}
+
+fn current_user_guilds(
+    client: &HyperClient,
+    options: &RequestOptions,
+    access_token: &str,
+    query: &CurrentUserGuildsQuery,
+) -> Result<Vec<CurrentUserGuild>> {
+    let path = format!(
+        "/users/@me/guilds?{}",
+        serde_urlencoded::to_string(query)?,
+    );
+
+    let response = api_request(
+        client,
+        options,
+        Method::Get,
+        &path,
+        ApiAuth::Bearer(access_token),
+        None,
+    )?;
+
+    serde_json::from_reader(response).map_err(From::from)
+}
+
+fn delete_webhook(
+    client: &HyperClient,
+    options: &RequestOptions,
+    webhook: &WebhookCredentials,
+) -> Result<()> {
+    api_request(
+        client,
+        options,
+        Method::Delete,
+        &format!("/webhooks/{}/{}", webhook.id, webhook.token),
+        ApiAuth::None,
+        None,
+    ).map(|_| ())
+}
+
+fn delete_webhook_message(
+    client: &HyperClient,
+    options: &RequestOptions,
+    webhook: &WebhookCredentials,
+    message_id: u64,
+) -> Result<()> {
+    api_request(
+        client,
+        options,
+        Method::Delete,
+        &format!(
+            "/webhooks/{}/{}/messages/{}",
+            webhook.id,
+            webhook.token,
+            message_id,
+        ),
+        ApiAuth::None,
+        None,
+    ).map(|_| ())
+}
+
+fn edit_webhook_message(
+    client: &HyperClient,
+    options: &RequestOptions,
+    webhook: &WebhookCredentials,
+    message_id: u64,
+    request: &EditWebhookMessageRequest,
+) -> Result<WebhookMessage> {
+    let response = api_request(
+        client,
+        options,
+        Method::Patch,
+        &format!(
+            "/webhooks/{}/{}/messages/{}",
+            webhook.id,
+            webhook.token,
+            message_id,
+        ),
+        ApiAuth::None,
+        Some(serde_json::to_string(request)?),
+    )?;
+
+    serde_json::from_reader(response).map_err(From::from)
+}
+
+fn execute_webhook(
+    client: &HyperClient,
+    options: &RequestOptions,
+    webhook: &WebhookCredentials,
+    request: &ExecuteWebhookRequest,
+    wait: bool,
+) -> Result<Response> {
+    api_request(
+        client,
+        options,
+        Method::Post,
+        &format!("/webhooks/{}/{}?wait={}", webhook.id, webhook.token, wait),
+        ApiAuth::None,
+        Some(serde_json::to_string(request)?),
+    )
+}
+
+/// Retrieves a JSON document from an absolute URL that does not require
+/// authorization.
+fn get_json<T>(client: &HyperClient, url: &str) -> Result<T>
+    where T: DeserializeOwned {
+    let response = client.get(url).send()?;
+
+    if !response.status.is_success() {
+        return Err(Error::Status(response.status));
+    }
+
+    serde_json::from_reader(response).map_err(From::from)
+}
+
+fn update_current_user_role_connection(
+    client: &HyperClient,
+    options: &RequestOptions,
+    access_token: &str,
+    application_id: u64,
+    connection: &ApplicationRoleConnection,
+) -> Result<ApplicationRoleConnection> {
+    let response = api_request(
+        client,
+        options,
+        Method::Put,
+        &format!("/users/@me/applications/{}/role-connection", application_id),
+        ApiAuth::Bearer(access_token),
+        Some(serde_json::to_string(connection)?),
+    )?;
+
+    serde_json::from_reader(response).map_err(From::from)
+}
+
+fn update_role_connection_metadata(
+    client: &HyperClient,
+    options: &RequestOptions,
+    bot_token: &str,
+    application_id: u64,
+    records: &[ApplicationRoleConnectionMetadata],
+) -> Result<Vec<ApplicationRoleConnectionMetadata>> {
+    let response = api_request(
+        client,
+        options,
+        Method::Put,
+        &format!("/applications/{}/role-connections/metadata", application_id),
+        ApiAuth::Bot(bot_token),
+        Some(serde_json::to_string(records)?),
+    )?;
+
+    serde_json::from_reader(response).map_err(From::from)
+}
+
+/// A request body sent to the token endpoint, authenticating the application.
+trait TokenRequest: Serialize {
+    fn client_id(&self) -> u64;
+
+    fn client_secret(&self) -> &str;
+
+    /// Encodes the body without the client secret, for when the application
+    /// authenticates using HTTP Basic authentication instead.
+    fn encode_without_secret(&self) -> Result<String>;
+}
+
+#[derive(Serialize)]
+struct AccessTokenExchangeBody<'a> {
+    client_id: u64,
+    code: &'a str,
+    grant_type: &'a str,
+    redirect_uri: &'a str,
+}
+
+#[derive(Serialize)]
+struct ClientCredentialsBody<'a> {
+    client_id: u64,
+    grant_type: &'a str,
+    scope: &'a str,
+}
+
+#[derive(Serialize)]
+struct RefreshTokenBody<'a> {
+    client_id: u64,
+    grant_type: &'a str,
+    redirect_uri: &'a str,
+    refresh_token: &'a str,
+}
+
+impl TokenRequest for AccessTokenExchangeRequest {
+    fn client_id(&self) -> u64 {
+        self.client_id
+    }
+
+    fn client_secret(&self) -> &str {
+        &self.client_secret
+    }
+
+    fn encode_without_secret(&self) -> Result<String> {
+        serde_urlencoded::to_string(AccessTokenExchangeBody {
+            client_id: self.client_id,
+            code: &self.code,
+            grant_type: &self.grant_type,
+            redirect_uri: &self.redirect_uri,
+        }).map_err(From::from)
+    }
+}
+
+impl TokenRequest for ClientCredentialsRequest {
+    fn client_id(&self) -> u64 {
+        self.client_id
+    }
+
+    fn client_secret(&self) -> &str {
+        &self.client_secret
+    }
+
+    fn encode_without_secret(&self) -> Result<String> {
+        serde_urlencoded::to_string(ClientCredentialsBody {
+            client_id: self.client_id,
+            grant_type: &self.grant_type,
+            scope: &self.scope,
+        }).map_err(From::from)
+    }
+}
+
+impl TokenRequest for RefreshTokenRequest {
+    fn client_id(&self) -> u64 {
+        self.client_id
+    }
+
+    fn client_secret(&self) -> &str {
+        &self.client_secret
+    }
+
+    fn encode_without_secret(&self) -> Result<String> {
+        serde_urlencoded::to_string(RefreshTokenBody {
+            client_id: self.client_id,
+            grant_type: &self.grant_type,
+            redirect_uri: &self.redirect_uri,
+            refresh_token: &self.refresh_token,
+        }).map_err(From::from)
+    }
+}
+
+/// The bytes that are percent-encoded by the
+/// `application/x-www-form-urlencoded` serializer: all but ASCII alphanumerics
+/// and `*`, `-`, `.` and `_`.
+#[derive(Clone)]
+struct FormEncodeSet;
+
+impl EncodeSet for FormEncodeSet {
+    fn contains(&self, byte: u8) -> bool {
+        match byte {
+            b'*' | b'-' | b'.' | b'_' => false,
+            _ => !byte.is_ascii_alphanumeric(),
+        }
+    }
+}
+
+// Both the code and the refresh token exchanges are sent form-encoded, as the
+// token endpoint does not accept JSON bodies (RFC 6749, sections 4.1.3 and 6).
+fn post_token<T, U>(
+    client: &HyperClient,
+    options: &RequestOptions,
+    request: &T,
+) -> Result<U> where T: TokenRequest, U: DeserializeOwned {
+    let mut headers = Headers::new();
+    headers.set(ContentType::form_url_encoded());
+
+    let body = if options.client_auth == ClientAuthMethod::Basic {
+        // The credentials are form-encoded before being used as the username
+        // and password (RFC 6749, section 2.3.1).
+        let secret = percent_encode(
+            request.client_secret().as_bytes(),
+            FormEncodeSet,
+        );
+
+        headers.set(Authorization(Basic {
+            username: request.client_id().to_string(),
+            password: Some(secret.to_string()),
+        }));
+
+        request.encode_without_secret()?
+    } else {
+        serde_urlencoded::to_string(request)?
+    };
+
+    let response = client.post(&options.endpoints.token_url())
+        .headers(headers)
+        .body(Body::BufBody(body.as_bytes(), body.len()))
+        .send()?;
+
+    parse_token_response(response)
+}
+
+fn parse_token_response<T>(response: Response) -> Result<T>
+    where T: DeserializeOwned {
+    if response.status.is_success() {
+        return serde_json::from_reader(response).map_err(From::from);
+    }
+
+    let status = response.status;
+
+    match serde_json::from_reader::<_, TokenErrorResponse>(response) {
+        Ok(error) => Err(Error::TokenRequest(error)),
+        Err(_) => Err(Error::Status(status)),
     }
 }
@@ -4,3 +4,35 @@
 //! functions that create one-off clients for ease of use.
 
 pub mod hyper;
+
+use super::Endpoints;
+
+/// The method used to authenticate your application to the token endpoint.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ClientAuthMethod {
+    /// Sends the client ID and secret as part of the request body.
+    ///
+    /// This is also known as `client_secret_post`.
+    RequestBody,
+    /// Sends the client ID and secret in an `Authorization: Basic` header,
+    /// omitting the secret from the request body.
+    ///
+    /// This is also known as `client_secret_basic`, and prevents the secret
+    /// from ending up in logged request bodies.
+    Basic,
+}
+
+impl Default for ClientAuthMethod {
+    fn default() -> Self {
+        ClientAuthMethod::RequestBody
+    }
+}
+
+/// Options configuring how requests to Discord's API are made.
+#[derive(Clone, Debug, Default)]
+pub struct RequestOptions {
+    /// The method used to authenticate your application to the token endpoint.
+    pub client_auth: ClientAuthMethod,
+    /// The URLs that requests are made to.
+    pub endpoints: Endpoints,
+}
@@ -0,0 +1,155 @@
+//! Caching of access tokens retrieved using the client credentials grant.
+
+use bridge::hyper::DiscordOAuthHyperRequester;
+use model::{ClientCredentialsAccessTokenResponse, ClientCredentialsRequest};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use super::{Result, Scope};
+
+/// The default amount of time before a token's expiry at which it is no longer
+/// handed out, and a new token is retrieved instead.
+pub const DEFAULT_LEEWAY: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+struct CachedToken {
+    expires_at: Instant,
+    response: ClientCredentialsAccessTokenResponse,
+}
+
+/// A cache of access tokens retrieved using the client credentials grant,
+/// keyed by client ID and set of requested scopes.
+///
+/// Cached tokens are handed out until shortly before they expire, after which
+/// a new token is transparently retrieved. The cache can be shared across
+/// threads, for example by wrapping it in an `Arc`.
+///
+/// # Examples
+///
+/// Retrieve a token, reusing it on subsequent calls:
+///
+/// ```rust,no_run
+/// extern crate hyper;
+/// extern crate serenity_oauth;
+///
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// use hyper::Client;
+/// use serenity_oauth::cache::ClientCredentialsCache;
+/// use serenity_oauth::model::ClientCredentialsRequest;
+/// use serenity_oauth::Scope;
+///
+/// let client = Client::new();
+/// let cache = ClientCredentialsCache::new();
+/// let request_data = ClientCredentialsRequest::new(
+///     249608697955745802,
+///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+///     &[Scope::Identify],
+/// );
+///
+/// let first = cache.get(&client, &request_data)?;
+/// let second = cache.get(&client, &request_data)?;
+///
+/// assert_eq!(first.access_token, second.access_token);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ClientCredentialsCache {
+    leeway: Duration,
+    tokens: Mutex<HashMap<(u64, BTreeSet<Scope>), Slot>>,
+}
+
+/// The cached token for a single key, locked while a new token is retrieved.
+type Slot = Arc<Mutex<Option<CachedToken>>>;
+
+impl ClientCredentialsCache {
+    /// Creates a new, empty cache using the [`DEFAULT_LEEWAY`].
+    ///
+    /// [`DEFAULT_LEEWAY`]: constant.DEFAULT_LEEWAY.html
+    pub fn new() -> Self {
+        Self::with_leeway(DEFAULT_LEEWAY)
+    }
+
+    /// Creates a new, empty cache that stops handing out tokens the given
+    /// amount of time before they expire.
+    pub fn with_leeway(leeway: Duration) -> Self {
+        Self {
+            tokens: Mutex::new(HashMap::new()),
+            leeway,
+        }
+    }
+
+    /// Retrieves a token for the client ID and scopes of the given request.
+    ///
+    /// If a cached token is still valid it is returned, with its `expires_in`
+    /// adjusted to the remaining lifetime. Otherwise, a new token is retrieved
+    /// using the requester and cached.
+    ///
+    /// Only the token for the requested client ID and scopes is locked while a
+    /// new token is retrieved, so concurrent callers for the same key wait for
+    /// the same request instead of each sending their own, while callers for
+    /// other keys are not held up.
+    pub fn get<R>(&self, requester: &R, request: &ClientCredentialsRequest)
+        -> Result<ClientCredentialsAccessTokenResponse>
+        where R: DiscordOAuthHyperRequester {
+        let key = cache_key(request.client_id, &request.scope);
+        let slot = lock(&self.tokens).entry(key).or_default().clone();
+        let mut cached = lock(&slot);
+        let now = Instant::now();
+
+        if let Some(ref token) = *cached {
+            if token.expires_at > now + self.leeway {
+                let mut response = token.response.clone();
+                response.expires_in = (token.expires_at - now).as_secs();
+
+                return Ok(response);
+            }
+        }
+
+        let response = requester.exchange_client_credentials(request)?;
+
+        *cached = Some(CachedToken {
+            expires_at: now + Duration::from_secs(response.expires_in),
+            response: response.clone(),
+        });
+
+        Ok(response)
+    }
+
+    /// Removes the cached token for the given client ID and scopes, if there
+    /// is one.
+    ///
+    /// This is useful when a token has been rejected before its expiry.
+    pub fn invalidate(&self, client_id: u64, scopes: &[Scope]) {
+        let key = (client_id, scopes.iter().cloned().collect());
+
+        lock(&self.tokens).remove(&key);
+    }
+
+    /// Removes all cached tokens.
+    pub fn clear(&self) {
+        lock(&self.tokens).clear();
+    }
+}
+
+impl Default for ClientCredentialsCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn cache_key(client_id: u64, scope: &str) -> (u64, BTreeSet<Scope>) {
+    (client_id, Scope::parse_list(scope).into_iter().collect())
+}
+
+// A panic while a lock is held can not leave the cache inconsistent, as tokens
+// are only ever replaced whole, so a poisoned lock is recovered from.
+fn lock<'a, T>(mutex: &'a Mutex<T>) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
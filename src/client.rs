@@ -0,0 +1,345 @@
+//! A configuration object holding your application's credentials.
+
+use bridge::hyper::{DiscordOAuthHyperRequester, HyperRequester};
+use bridge::RequestOptions;
+use hyper::client::Client as HyperClient;
+use model::{
+    AccessTokenExchangeRequest,
+    AccessTokenResponse,
+    ClientCredentialsAccessTokenResponse,
+    ClientCredentialsRequest,
+    Grant,
+    RefreshTokenRequest,
+    TokenErrorResponse,
+};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use store::TokenStore;
+use utils::{self, Permissions};
+use super::{Result, Scope};
+
+/// Your application's OAuth2 configuration.
+///
+/// This holds the credentials, redirect URIs, and default scopes of your
+/// application, so that only the per-user details need to be passed when
+/// building authorization URLs and performing grants.
+///
+/// # Examples
+///
+/// Create a client requesting the [`Scope::Identify`] and [`Scope::Email`]
+/// scopes, and produce an authorization URL for it:
+///
+/// ```rust
+/// use serenity_oauth::{OAuthClient, Scope};
+///
+/// let mut client = OAuthClient::new(
+///     249608697955745802,
+///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+///     "https://myapplication.website",
+/// );
+/// client.scopes = vec![Scope::Identify, Scope::Email];
+///
+/// let url = client.authorization_url(Some("15773059ghq9183habn"));
+///
+/// let expected = "https://discordapp.com/api/oauth2/authorize?response_type=code&client_id=249608697955745802&redirect_uri=https%3A%2F%2Fmyapplication.website&scope=identify%20email&state=15773059ghq9183habn";
+/// assert_eq!(url, expected);
+/// ```
+///
+/// [`Scope::Email`]: enum.Scope.html#variant.Email
+/// [`Scope::Identify`]: enum.Scope.html#variant.Identify
+#[derive(Clone)]
+pub struct OAuthClient {
+    /// Your application's client ID.
+    pub client_id: u64,
+    /// Your application's client secret.
+    ///
+    /// This is redacted from the `Debug` output.
+    pub client_secret: String,
+    /// The options for making requests, including the [`Endpoints`] used when
+    /// building authorization URLs.
+    ///
+    /// [`Endpoints`]: struct.Endpoints.html
+    pub options: RequestOptions,
+    // Never empty, as the first redirect URI is used by default.
+    redirect_uris: Vec<String>,
+    /// The scopes requested by default.
+    ///
+    /// This defaults to only [`Scope::Identify`].
+    ///
+    /// [`Scope::Identify`]: enum.Scope.html#variant.Identify
+    pub scopes: Vec<Scope>,
+}
+
+impl Debug for OAuthClient {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("OAuthClient")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"[redacted]")
+            .field("options", &self.options)
+            .field("redirect_uris", &self.redirect_uris)
+            .field("scopes", &self.scopes)
+            .finish()
+    }
+}
+
+impl OAuthClient {
+    /// Creates a new configuration with a single redirect URI, requesting only
+    /// the [`Scope::Identify`] scope by default.
+    ///
+    /// [`Scope::Identify`]: enum.Scope.html#variant.Identify
+    pub fn new<S, T>(client_id: u64, client_secret: S, redirect_uri: T) -> Self
+        where S: Into<String>, T: Into<String> {
+        Self {
+            client_secret: client_secret.into(),
+            options: RequestOptions::default(),
+            redirect_uris: vec![redirect_uri.into()],
+            scopes: vec![Scope::Identify],
+            client_id,
+        }
+    }
+
+    /// Wraps a hyper Client in a requester making requests with the
+    /// configured [`options`].
+    ///
+    /// [`options`]: #structfield.options
+    pub fn requester(&self, client: HyperClient) -> HyperRequester {
+        HyperRequester::new(client, self.options.clone())
+    }
+
+    /// The redirect URI used by default, as given to [`new`].
+    ///
+    /// [`new`]: #method.new
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uris[0]
+    }
+
+    /// The redirect URIs registered for your application, starting with the
+    /// one used by default.
+    pub fn redirect_uris(&self) -> &[String] {
+        &self.redirect_uris
+    }
+
+    /// Registers an additional redirect URI, which can be redirected to using
+    /// [`authorization_url_with_redirect`].
+    ///
+    /// [`authorization_url_with_redirect`]: #method.authorization_url_with_redirect
+    pub fn add_redirect_uri<S>(&mut self, redirect_uri: S)
+        where S: Into<String> {
+        self.redirect_uris.push(redirect_uri.into());
+    }
+
+    /// Creates a URL for an authorization code grant requesting the default
+    /// scopes, redirecting to the default redirect URI.
+    ///
+    /// Refer to [`utils::authorization_code_grant_url`] for more information.
+    ///
+    /// [`utils::authorization_code_grant_url`]: utils/fn.authorization_code_grant_url.html
+    pub fn authorization_url(&self, state: Option<&str>) -> String {
+        self.authorization_url_with_redirect(self.redirect_uri(), state)
+    }
+
+    /// Creates a URL for an authorization code grant requesting the default
+    /// scopes, redirecting to the given redirect URI.
+    pub fn authorization_url_with_redirect(
+        &self,
+        redirect_uri: &str,
+        state: Option<&str>,
+    ) -> String {
+        utils::authorization_code_grant_url_with_endpoints(
+            &self.options.endpoints,
+            self.client_id,
+            &self.scopes,
+            state,
+            redirect_uri,
+        )
+    }
+
+    /// Creates a URL for an OpenID Connect authorization code grant requesting
+    /// the default scopes, including a nonce that the returned ID token must
+    /// contain.
+    ///
+    /// Refer to [`utils::authorization_code_grant_url_with_nonce`] for more
+    /// information.
+    ///
+    /// [`utils::authorization_code_grant_url_with_nonce`]: utils/fn.authorization_code_grant_url_with_nonce.html
+    pub fn authorization_url_with_nonce(
+        &self,
+        state: Option<&str>,
+        nonce: &str,
+    ) -> String {
+        utils::authorization_code_grant_url_with_nonce(
+            &self.options.endpoints,
+            self.client_id,
+            &self.scopes,
+            state,
+            nonce,
+            self.redirect_uri(),
+        )
+    }
+
+    /// Creates a URL for a simple bot authorization flow, requesting the given
+    /// permissions.
+    ///
+    /// Refer to [`utils::bot_authorization_url`] for more information.
+    ///
+    /// [`utils::bot_authorization_url`]: utils/fn.bot_authorization_url.html
+    pub fn bot_authorization_url(&self, permissions: Permissions) -> String {
+        utils::bot_authorization_url_with_endpoints(
+            &self.options.endpoints,
+            self.client_id,
+            permissions,
+        )
+    }
+
+    /// Creates a request body for exchanging a code for an access token,
+    /// using the default redirect URI.
+    pub fn code_exchange_request<S>(&self, code: S)
+        -> AccessTokenExchangeRequest where S: Into<String> {
+        AccessTokenExchangeRequest::new(
+            self.client_id,
+            self.client_secret.clone(),
+            code,
+            self.redirect_uri(),
+        )
+    }
+
+    /// Creates a request body for exchanging a refresh token for a fresh
+    /// access token.
+    pub fn refresh_token_request<S>(&self, refresh_token: S)
+        -> RefreshTokenRequest where S: Into<String> {
+        RefreshTokenRequest::new(
+            self.client_id,
+            self.client_secret.clone(),
+            self.redirect_uri(),
+            refresh_token,
+        )
+    }
+
+    /// Creates a request body for retrieving an access token using the client
+    /// credentials grant, requesting the default scopes.
+    pub fn client_credentials_request(&self) -> ClientCredentialsRequest {
+        ClientCredentialsRequest::new(
+            self.client_id,
+            self.client_secret.clone(),
+            &self.scopes,
+        )
+    }
+
+    /// Exchanges a code for the user's access token, using the default
+    /// redirect URI.
+    ///
+    /// The request is made with the configured [`options`].
+    ///
+    /// # Examples
+    ///
+    /// Exchange a code for an access token:
+    ///
+    /// ```rust,no_run
+    /// extern crate hyper;
+    /// extern crate serenity_oauth;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use hyper::Client;
+    /// use serenity_oauth::OAuthClient;
+    ///
+    /// let oauth = OAuthClient::new(
+    ///     249608697955745802,
+    ///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+    ///     "https://myapplication.website",
+    /// );
+    ///
+    /// let client = Client::new();
+    /// let response = oauth.exchange_code(&client, "user code here")?;
+    ///
+    /// println!("Access token: {}", response.access_token);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`options`]: #structfield.options
+    pub fn exchange_code(&self, client: &HyperClient, code: &str)
+        -> Result<AccessTokenResponse> {
+        self.borrowed_requester(client)
+            .exchange_code(&self.code_exchange_request(code))
+    }
+
+    /// Exchanges a code for the user's access token, for an authorization URL
+    /// that used the given redirect URI.
+    pub fn exchange_code_with_redirect(
+        &self,
+        client: &HyperClient,
+        code: &str,
+        redirect_uri: &str,
+    ) -> Result<AccessTokenResponse> {
+        let mut request = self.code_exchange_request(code);
+        request.redirect_uri = redirect_uri.to_owned();
+
+        self.borrowed_requester(client).exchange_code(&request)
+    }
+
+    /// Exchanges a code for the user's access token along with any guild or
+    /// webhook that was authorized, using the default redirect URI.
+    ///
+    /// Refer to [`DiscordOAuthHyperRequester::exchange_grant`] for more
+    /// information.
+    ///
+    /// [`DiscordOAuthHyperRequester::exchange_grant`]: trait.DiscordOAuthHyperRequester.html#method.exchange_grant
+    pub fn exchange_grant(&self, client: &HyperClient, code: &str)
+        -> Result<Grant> {
+        self.borrowed_requester(client)
+            .exchange_grant(&self.code_exchange_request(code))
+    }
+
+    /// Exchanges a refresh token, returning a new refresh token and fresh
+    /// access token.
+    pub fn exchange_refresh_token(
+        &self,
+        client: &HyperClient,
+        refresh_token: &str,
+    ) -> Result<AccessTokenResponse> {
+        self.borrowed_requester(client)
+            .exchange_refresh_token(&self.refresh_token_request(refresh_token))
+    }
+
+    /// Exchanges the refresh token of a grant kept in a [`TokenStore`].
+    ///
+    /// Refer to [`DiscordOAuthHyperRequester::refresh_stored_token`] for more
+    /// information.
+    ///
+    /// [`DiscordOAuthHyperRequester::refresh_stored_token`]: trait.DiscordOAuthHyperRequester.html#method.refresh_stored_token
+    /// [`TokenStore`]: store/trait.TokenStore.html
+    pub fn refresh_stored_token<S, F>(
+        &self,
+        client: &HyperClient,
+        store: &S,
+        key: &str,
+        refresh_token: &str,
+        on_deauthorized: F,
+    ) -> Result<AccessTokenResponse>
+        where S: TokenStore, F: FnOnce(&str, &TokenErrorResponse) {
+        self.borrowed_requester(client).refresh_stored_token(
+            store,
+            key,
+            &self.refresh_token_request(refresh_token),
+            on_deauthorized,
+        )
+    }
+
+    /// Retrieves an access token for the owner of the application using the
+    /// client credentials grant, requesting the default scopes.
+    pub fn exchange_client_credentials(&self, client: &HyperClient)
+        -> Result<ClientCredentialsAccessTokenResponse> {
+        self.borrowed_requester(client)
+            .exchange_client_credentials(&self.client_credentials_request())
+    }
+
+    fn borrowed_requester<'a>(&self, client: &'a HyperClient)
+        -> HyperRequester<&'a HyperClient> {
+        HyperRequester::new(client, self.options.clone())
+    }
+}
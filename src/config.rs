@@ -0,0 +1,388 @@
+//! Loading of your application's configuration from environment variables and
+//! configuration files.
+//!
+//! The client secret can either be given directly or read from a secret file,
+//! such as those mounted by Docker and Kubernetes secrets.
+//!
+//! # Environment variables
+//!
+//! When loading from the environment with [`from_env`], the following variables
+//! are read:
+//!
+//! - `DISCORD_CLIENT_ID`: your application's client ID;
+//! - `DISCORD_CLIENT_SECRET`: your application's client secret, or
+//!   `DISCORD_CLIENT_SECRET_FILE`: the path to a file containing it;
+//! - `DISCORD_REDIRECT_URIS`: a comma-separated list of redirect URIs;
+//! - `DISCORD_SCOPES`: an optional space-separated list of default scopes;
+//! - `DISCORD_API_BASE_URL`: an optional base URL of the API, such as that of a
+//!   mock server;
+//! - `DISCORD_API_VERSION`: an optional version of the API to use.
+//!
+//! If neither the base URL nor version are given, the default [`Endpoints`] are
+//! used. If only a version is given, the [`Endpoints::discord`] preset is used
+//! with that version.
+//!
+//! # Configuration files
+//!
+//! TOML and JSON files contain the same keys as [`Config`]:
+//!
+//! ```toml
+//! client_id = 249608697955745802
+//! client_secret_file = "/run/secrets/discord_client_secret"
+//! redirect_uris = ["https://myapplication.website/callback"]
+//! scopes = ["identify", "email"]
+//! ```
+//!
+//! [`Config`]: struct.Config.html
+//! [`Endpoints`]: ../struct.Endpoints.html
+//! [`Endpoints::discord`]: ../struct.Endpoints.html#method.discord
+//! [`from_env`]: fn.from_env.html
+
+use serde_json::{self, Error as JsonError};
+use std::env;
+use std::error::Error as StdError;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::fs::File;
+use std::io::{Error as IoError, Read};
+use std::path::{Path, PathBuf};
+use toml::{self, de::Error as TomlError};
+use super::{Endpoints, Error, OAuthClient, Result, Scope};
+
+/// The prefix of the environment variables read by [`from_env`].
+///
+/// [`from_env`]: fn.from_env.html
+pub const DEFAULT_ENV_PREFIX: &str = "DISCORD";
+
+/// Your application's configuration, as read from a configuration file.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Config {
+    /// The base URL of the API, such as that of a mock server.
+    #[serde(default)]
+    pub api_base_url: Option<String>,
+    /// The version of the API to use.
+    #[serde(default)]
+    pub api_version: Option<u8>,
+    /// Your application's client ID.
+    pub client_id: u64,
+    /// Your application's client secret.
+    ///
+    /// Exactly one of this and [`client_secret_file`] must be given.
+    ///
+    /// This is redacted from the `Debug` output, and never serialized.
+    ///
+    /// [`client_secret_file`]: #structfield.client_secret_file
+    #[serde(default, skip_serializing)]
+    pub client_secret: Option<String>,
+    /// The path to a file containing your application's client secret.
+    ///
+    /// Surrounding whitespace in the file is ignored.
+    #[serde(default)]
+    pub client_secret_file: Option<PathBuf>,
+    /// The redirect URIs registered for your application.
+    ///
+    /// At least one redirect URI must be given. The first is used by default.
+    pub redirect_uris: Vec<String>,
+    /// The scopes requested by default.
+    ///
+    /// If none are given, only [`Scope::Identify`] is requested.
+    ///
+    /// [`Scope::Identify`]: ../enum.Scope.html#variant.Identify
+    #[serde(default)]
+    pub scopes: Vec<Scope>,
+}
+
+impl Debug for Config {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let client_secret = self.client_secret.as_ref().map(|_| "[redacted]");
+
+        f.debug_struct("Config")
+            .field("api_base_url", &self.api_base_url)
+            .field("api_version", &self.api_version)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &client_secret)
+            .field("client_secret_file", &self.client_secret_file)
+            .field("redirect_uris", &self.redirect_uris)
+            .field("scopes", &self.scopes)
+            .finish()
+    }
+}
+
+impl Config {
+    /// Validates the configuration, reading the client secret file if one was
+    /// given, and produces an [`OAuthClient`].
+    ///
+    /// [`OAuthClient`]: ../struct.OAuthClient.html
+    pub fn into_client(self) -> Result<OAuthClient> {
+        let secret = (self.client_secret, self.client_secret_file);
+
+        let client_secret = match secret {
+            (Some(secret), None) => secret,
+            (None, Some(path)) => read_secret_file(&path)?,
+            (None, None) => return Err(missing("client_secret")),
+            (Some(_), Some(_)) => {
+                let reason = "client_secret_file must not also be given";
+
+                return Err(malformed("client_secret", reason));
+            },
+        };
+
+        if client_secret.is_empty() {
+            return Err(malformed("client_secret", "must not be empty"));
+        }
+
+        let mut redirect_uris = self.redirect_uris.into_iter();
+        let redirect_uri = match redirect_uris.next() {
+            Some(redirect_uri) => redirect_uri,
+            None => return Err(missing("redirect_uris")),
+        };
+
+        let mut client = OAuthClient::new(
+            self.client_id,
+            client_secret,
+            redirect_uri,
+        );
+
+        for redirect_uri in redirect_uris {
+            client.add_redirect_uri(redirect_uri);
+        }
+
+        if !self.scopes.is_empty() {
+            client.scopes = self.scopes;
+        }
+
+        client.options.endpoints = match (self.api_base_url, self.api_version) {
+            (None, None) => Endpoints::default(),
+            (None, version @ Some(_)) => Endpoints {
+                api_version: version,
+                ..Endpoints::discord()
+            },
+            (Some(base_url), version) => Endpoints {
+                api_version: version,
+                ..Endpoints::with_base_url(base_url)
+            },
+        };
+
+        Ok(client)
+    }
+}
+
+/// An error that occurred while loading a configuration.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// A file could not be read.
+    Io(PathBuf, IoError),
+    /// A JSON configuration file could not be parsed.
+    Json(JsonError),
+    /// A value was given, but is invalid.
+    Malformed {
+        /// The name of the key or environment variable.
+        key: String,
+        /// A description of why the value is invalid.
+        reason: String,
+    },
+    /// A required value was not given.
+    ///
+    /// This contains the name of the key or environment variable.
+    Missing(String),
+    /// A TOML configuration file could not be parsed.
+    Toml(TomlError),
+    /// The format of a configuration file could not be determined from its
+    /// extension.
+    ///
+    /// Supported extensions are `.json` and `.toml`.
+    UnknownFormat(PathBuf),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            ConfigError::Io(ref path, ref inner) => {
+                write!(f, "Error reading {}: {}", path.display(), inner)
+            },
+            ConfigError::Json(ref inner) => Display::fmt(inner, f),
+            ConfigError::Malformed { ref key, ref reason } => {
+                write!(f, "Invalid value for {}: {}", key, reason)
+            },
+            ConfigError::Missing(ref key) => write!(f, "Missing {}", key),
+            ConfigError::Toml(ref inner) => Display::fmt(inner, f),
+            ConfigError::UnknownFormat(ref path) => {
+                write!(f, "Unknown configuration format: {}", path.display())
+            },
+        }
+    }
+}
+
+impl StdError for ConfigError {
+    fn description(&self) -> &str {
+        match *self {
+            ConfigError::Io(_, ref inner) => inner.description(),
+            ConfigError::Json(ref inner) => inner.description(),
+            ConfigError::Malformed { .. } => "Malformed configuration value",
+            ConfigError::Missing(_) => "Missing configuration value",
+            ConfigError::Toml(ref inner) => inner.description(),
+            ConfigError::UnknownFormat(_) => "Unknown configuration format",
+        }
+    }
+}
+
+/// Loads your application's configuration from the environment variables
+/// prefixed with [`DEFAULT_ENV_PREFIX`].
+///
+/// Refer to the [module-level documentation] for the variables that are read.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::env;
+///
+/// env::set_var("DISCORD_CLIENT_ID", "249608697955745802");
+/// env::set_var("DISCORD_CLIENT_SECRET", "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4");
+/// env::set_var("DISCORD_REDIRECT_URIS", "https://myapplication.website");
+///
+/// let client = serenity_oauth::config::from_env().unwrap();
+///
+/// assert_eq!(client.client_id, 249608697955745802);
+/// assert_eq!(client.redirect_uri(), "https://myapplication.website");
+/// ```
+///
+/// [`DEFAULT_ENV_PREFIX`]: constant.DEFAULT_ENV_PREFIX.html
+/// [module-level documentation]: index.html#environment-variables
+pub fn from_env() -> Result<OAuthClient> {
+    from_env_with_prefix(DEFAULT_ENV_PREFIX)
+}
+
+/// Loads your application's configuration from environment variables with the
+/// given prefix, such as `DISCORD_STAGING` for `DISCORD_STAGING_CLIENT_ID`.
+pub fn from_env_with_prefix(prefix: &str) -> Result<OAuthClient> {
+    let var = |name: &str| {
+        let key = format!("{}_{}", prefix, name);
+        let value = env::var(&key).ok().filter(|value| !value.is_empty());
+
+        (key, value)
+    };
+
+    let client_id = match var("CLIENT_ID") {
+        (key, Some(value)) => value.trim().parse::<u64>().map_err(|why| {
+            malformed(&key, &why.to_string())
+        })?,
+        (key, None) => return Err(missing(&key)),
+    };
+
+    let (secret_key, secret) = var("CLIENT_SECRET");
+    let (secret_file_key, secret_file) = var("CLIENT_SECRET_FILE");
+
+    let client_secret = match (secret, secret_file) {
+        (Some(secret), None) => secret,
+        (None, Some(path)) => read_secret_file(Path::new(&path))?,
+        (None, None) => return Err(missing(&secret_key)),
+        (Some(_), Some(_)) => {
+            let reason = format!("{} must not also be set", secret_file_key);
+
+            return Err(malformed(&secret_key, &reason));
+        },
+    };
+
+    let redirect_uris = match var("REDIRECT_URIS") {
+        (key, Some(value)) => {
+            let uris = value.split(',')
+                .map(|uri| uri.trim().to_owned())
+                .filter(|uri| !uri.is_empty())
+                .collect::<Vec<_>>();
+
+            if uris.is_empty() {
+                return Err(missing(&key));
+            }
+
+            uris
+        },
+        (key, None) => return Err(missing(&key)),
+    };
+
+    let scopes = var("SCOPES").1
+        .map(|value| Scope::parse_list(&value))
+        .unwrap_or_default();
+
+    let api_version = match var("API_VERSION") {
+        (key, Some(value)) => Some(value.trim().parse::<u8>().map_err(|why| {
+            malformed(&key, &why.to_string())
+        })?),
+        (_, None) => None,
+    };
+
+    Config {
+        api_base_url: var("API_BASE_URL").1,
+        client_secret: Some(client_secret),
+        client_secret_file: None,
+        api_version,
+        client_id,
+        redirect_uris,
+        scopes,
+    }.into_client()
+}
+
+/// Loads your application's configuration from a TOML or JSON file, depending
+/// on its extension.
+pub fn from_file<P: AsRef<Path>>(path: P) -> Result<OAuthClient> {
+    let path = path.as_ref();
+    let contents = read_file(path)?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => from_json_str(&contents),
+        Some("toml") => from_toml_str(&contents),
+        _ => Err(Error::Config(ConfigError::UnknownFormat(path.to_owned()))),
+    }
+}
+
+/// Loads your application's configuration from a JSON string.
+pub fn from_json_str(json: &str) -> Result<OAuthClient> {
+    serde_json::from_str::<Config>(json)
+        .map_err(|why| Error::Config(ConfigError::Json(why)))?
+        .into_client()
+}
+
+/// Loads your application's configuration from a TOML string.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_oauth::Scope;
+///
+/// let client = serenity_oauth::config::from_toml_str(r#"
+///     client_id = 249608697955745802
+///     client_secret = "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4"
+///     redirect_uris = ["https://myapplication.website"]
+///     scopes = ["identify", "guilds"]
+/// "#).unwrap();
+///
+/// assert_eq!(client.scopes, vec![Scope::Identify, Scope::Guilds]);
+/// ```
+pub fn from_toml_str(toml: &str) -> Result<OAuthClient> {
+    toml::from_str::<Config>(toml)
+        .map_err(|why| Error::Config(ConfigError::Toml(why)))?
+        .into_client()
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    let mut contents = String::new();
+
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .map_err(|why| Error::Config(ConfigError::Io(path.to_owned(), why)))?;
+
+    Ok(contents)
+}
+
+fn read_secret_file(path: &Path) -> Result<String> {
+    read_file(path).map(|secret| secret.trim().to_owned())
+}
+
+fn malformed(key: &str, reason: &str) -> Error {
+    Error::Config(ConfigError::Malformed {
+        key: key.to_owned(),
+        reason: reason.to_owned(),
+    })
+}
+
+fn missing(key: &str) -> Error {
+    Error::Config(ConfigError::Missing(key.to_owned()))
+}
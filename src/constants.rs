@@ -1,5 +1,14 @@
 //! A set of constants around the OAuth2 API.
+//!
+//! These match the [`Endpoints::discordapp`] preset. To make requests to other
+//! URLs, such as Discord's versioned API or a mock server, configure
+//! [`Endpoints`] instead.
+//!
+//! [`Endpoints`]: ../struct.Endpoints.html
+//! [`Endpoints::discordapp`]: ../struct.Endpoints.html#method.discordapp
 
+/// The base URI of Discord's CDN, used for user avatars and guild icons.
+pub const BASE_CDN_URI: &str = "https://cdn.discordapp.com";
 /// The base authorization URI, used for authorizing an application.
 pub const BASE_AUTHORIZE_URI: &str = "https://discordapp.com/api/oauth2/authorize";
 /// The revocation URL, used to revoke an access token.
@@ -0,0 +1,186 @@
+/// The base URL of Discord's legacy API.
+const DISCORDAPP_BASE_URL: &str = "https://discordapp.com/api";
+/// The base URL of Discord's API.
+const DISCORD_BASE_URL: &str = "https://discord.com/api";
+/// The base URL of Discord's canary API.
+const DISCORD_CANARY_BASE_URL: &str = "https://canary.discord.com/api";
+/// The base URL of Discord's website, which serves the authorization page.
+const DISCORD_WEBSITE_URL: &str = "https://discord.com";
+/// The base URL of Discord's canary website.
+const DISCORD_CANARY_WEBSITE_URL: &str = "https://canary.discord.com";
+/// The version of Discord's API used by the [`Endpoints::discord`] and
+/// [`Endpoints::canary`] presets.
+///
+/// [`Endpoints::canary`]: struct.Endpoints.html#method.canary
+/// [`Endpoints::discord`]: struct.Endpoints.html#method.discord
+const DISCORD_API_VERSION: u8 = 10;
+
+/// Configuration of the URLs that requests to Discord's API are made to.
+///
+/// URLs are built from the [`base_url`], followed by `/v{api_version}` if an
+/// [`api_version`] is set, followed by the path of the endpoint.
+///
+/// The authorization URL is the exception: it is a page the user is sent to
+/// rather than an API endpoint, so it is never versioned, and is built from
+/// the [`authorize_base_url`] if one is set.
+///
+/// The default is the [`Endpoints::discordapp`] preset, matching the URLs in
+/// the [`constants`] module.
+///
+/// # Examples
+///
+/// Point requests at a mock server used during integration tests:
+///
+/// ```rust
+/// use serenity_oauth::Endpoints;
+///
+/// let endpoints = Endpoints::with_base_url("http://localhost:8080/api");
+///
+/// assert_eq!(endpoints.token_url(), "http://localhost:8080/api/oauth2/token");
+/// ```
+///
+/// [`Endpoints::discordapp`]: #method.discordapp
+/// [`api_version`]: #structfield.api_version
+/// [`authorize_base_url`]: #structfield.authorize_base_url
+/// [`base_url`]: #structfield.base_url
+/// [`constants`]: constants/index.html
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Endpoints {
+    /// The version of the API to use, if any.
+    #[serde(default)]
+    pub api_version: Option<u8>,
+    /// The base URL of the authorization page, without a trailing slash, if it
+    /// differs from the [`base_url`].
+    ///
+    /// [`base_url`]: #structfield.base_url
+    #[serde(default)]
+    pub authorize_base_url: Option<String>,
+    /// The path of the authorization endpoint.
+    pub authorize_path: String,
+    /// The base URL of the API, without a trailing slash.
+    pub base_url: String,
+    /// The path of the token revocation endpoint.
+    pub revoke_path: String,
+    /// The path of the token endpoint.
+    pub token_path: String,
+}
+
+impl Endpoints {
+    /// The unversioned `discordapp.com` API, as used by the [`constants`]
+    /// module.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_oauth::constants::{BASE_REVOKE_URI, BASE_TOKEN_URI};
+    /// use serenity_oauth::Endpoints;
+    ///
+    /// let endpoints = Endpoints::discordapp();
+    ///
+    /// assert_eq!(endpoints.revoke_url(), BASE_REVOKE_URI);
+    /// assert_eq!(endpoints.token_url(), BASE_TOKEN_URI);
+    /// ```
+    ///
+    /// [`constants`]: constants/index.html
+    pub fn discordapp() -> Self {
+        let mut endpoints = Self::with_base_url(DISCORDAPP_BASE_URL);
+        endpoints.revoke_path = "/oauth2/revoke".to_owned();
+
+        endpoints
+    }
+
+    /// The current, versioned `discord.com` API.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_oauth::Endpoints;
+    ///
+    /// let endpoints = Endpoints::discord();
+    ///
+    /// assert_eq!(endpoints.authorize_url(), "https://discord.com/oauth2/authorize");
+    /// assert_eq!(endpoints.token_url(), "https://discord.com/api/v10/oauth2/token");
+    /// ```
+    pub fn discord() -> Self {
+        let mut endpoints = Self::with_base_url(DISCORD_BASE_URL);
+        endpoints.api_version = Some(DISCORD_API_VERSION);
+        endpoints.authorize_base_url = Some(DISCORD_WEBSITE_URL.to_owned());
+
+        endpoints
+    }
+
+    /// The current, versioned API of Discord's canary build.
+    pub fn canary() -> Self {
+        let mut endpoints = Self::with_base_url(DISCORD_CANARY_BASE_URL);
+        endpoints.api_version = Some(DISCORD_API_VERSION);
+        endpoints.authorize_base_url = Some(
+            DISCORD_CANARY_WEBSITE_URL.to_owned(),
+        );
+
+        endpoints
+    }
+
+    /// An unversioned API at the given base URL, using the current paths of
+    /// the OAuth2 endpoints.
+    ///
+    /// This is useful for pointing requests at a proxy or mock server.
+    pub fn with_base_url<S: Into<String>>(base_url: S) -> Self {
+        let mut base_url = base_url.into();
+
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+
+        Self {
+            api_version: None,
+            authorize_base_url: None,
+            authorize_path: "/oauth2/authorize".to_owned(),
+            revoke_path: "/oauth2/token/revoke".to_owned(),
+            token_path: "/oauth2/token".to_owned(),
+            base_url,
+        }
+    }
+
+    /// Produces the full URL of an API endpoint with the given path, such as
+    /// `/users/@me`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_oauth::Endpoints;
+    ///
+    /// let url = Endpoints::discord().url("/users/@me");
+    ///
+    /// assert_eq!(url, "https://discord.com/api/v10/users/@me");
+    /// ```
+    pub fn url(&self, path: &str) -> String {
+        match self.api_version {
+            Some(version) => format!("{}/v{}{}", self.base_url, version, path),
+            None => format!("{}{}", self.base_url, path),
+        }
+    }
+
+    /// The full URL of the authorization endpoint.
+    pub fn authorize_url(&self) -> String {
+        let base_url = self.authorize_base_url.as_ref()
+            .unwrap_or(&self.base_url);
+
+        format!("{}{}", base_url, self.authorize_path)
+    }
+
+    /// The full URL of the token revocation endpoint.
+    pub fn revoke_url(&self) -> String {
+        self.url(&self.revoke_path)
+    }
+
+    /// The full URL of the token endpoint.
+    pub fn token_url(&self) -> String {
+        self.url(&self.token_path)
+    }
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self::discordapp()
+    }
+}
@@ -1,4 +1,10 @@
+use config::ConfigError;
+use hyper::status::StatusCode;
 use hyper::Error as HyperError;
+use jwt::JwtError;
+use model::{ApiErrorResponse, TokenErrorResponse};
+use oidc::IdTokenError;
+use seal::SealError;
 use serde_json::Error as JsonError;
 use serde_urlencoded::ser::Error as UrlEncodeError;
 use std::error::Error as StdError;
@@ -11,26 +17,126 @@ pub type Result<T> = StdResult<T, Error>;
 /// Standard error enum used to wrap different potential error types.
 #[derive(Debug)]
 pub enum Error {
+    /// Discord's API responded with an error, along with the status code of
+    /// the response.
+    Api(StatusCode, ApiErrorResponse),
+    /// An error while loading a configuration.
+    Config(ConfigError),
     /// An error from the `hyper` crate.
     Hyper(HyperError),
+    /// An OpenID Connect ID token failed validation.
+    IdToken(IdTokenError),
+    /// A public key used to verify signatures is not a valid hex-encoded
+    /// Ed25519 public key.
+    InvalidPublicKey,
+    /// The signature of a request is missing, malformed, or does not match
+    /// the request.
+    InvalidSignature,
     /// An error from the `serde_json` crate.
     Json(JsonError),
+    /// A JSON Web Token failed verification.
+    Jwt(JwtError),
+    /// Your bot is missing permissions required to perform the request.
+    MissingPermissions(ApiErrorResponse),
+    /// An access token used in the request was not granted a scope required to
+    /// perform the request.
+    MissingScope(ApiErrorResponse),
+    /// A session could not be sealed or opened.
+    Seal(SealError),
+    /// The timestamp of a signed request is too far from the current time,
+    /// such as when a captured request is replayed.
+    StaleTimestamp,
+    /// The server responded with an unsuccessful status code and a body that
+    /// could not be parsed.
+    Status(StatusCode),
+    /// The token endpoint rejected the grant.
+    ///
+    /// Use [`Error::is_revoked`] to check whether the grant has permanently
+    /// been invalidated.
+    ///
+    /// [`Error::is_revoked`]: #method.is_revoked
+    TokenRequest(TokenErrorResponse),
     /// An error from the `serde_urlencoded` crate.
     UrlEncode(UrlEncodeError),
 }
 
+impl Error {
+    /// Whether the error indicates that the user's grant has permanently been
+    /// invalidated, such as when they deauthorized the application.
+    ///
+    /// Requests failing with such an error should not be retried.
+    pub fn is_revoked(&self) -> bool {
+        match *self {
+            Error::TokenRequest(ref inner) => inner.is_revoked(),
+            _ => false,
+        }
+    }
+
+    /// Whether the error is likely to be temporary, and the request may succeed
+    /// if retried later.
+    ///
+    /// This is the case for network errors, ratelimits, and server errors,
+    /// including those reported by the token endpoint.
+    pub fn is_transient(&self) -> bool {
+        match *self {
+            Error::Hyper(_) => true,
+            Error::TokenRequest(ref inner) => inner.is_transient(),
+            Error::Api(status, _) | Error::Status(status) => {
+                status == StatusCode::TooManyRequests
+                    || status.is_server_error()
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether the request was rejected because the access token used is
+    /// invalid, such as when it has expired or been revoked.
+    pub fn is_unauthorized(&self) -> bool {
+        match *self {
+            Error::Api(status, _) | Error::Status(status) => {
+                status == StatusCode::Unauthorized
+            },
+            _ => false,
+        }
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(err: ConfigError) -> Self {
+        Error::Config(err)
+    }
+}
+
 impl From<HyperError> for Error {
     fn from(err: HyperError) -> Self {
         Error::Hyper(err)
     }
 }
 
+impl From<IdTokenError> for Error {
+    fn from(err: IdTokenError) -> Self {
+        Error::IdToken(err)
+    }
+}
+
 impl From<JsonError> for Error {
     fn from(err: JsonError) -> Self {
         Error::Json(err)
     }
 }
 
+impl From<JwtError> for Error {
+    fn from(err: JwtError) -> Self {
+        Error::Jwt(err)
+    }
+}
+
+impl From<SealError> for Error {
+    fn from(err: SealError) -> Self {
+        Error::Seal(err)
+    }
+}
+
 impl From<UrlEncodeError> for Error {
     fn from(err: UrlEncodeError) -> Self {
         Error::UrlEncode(err)
@@ -39,15 +145,35 @@ impl From<UrlEncodeError> for Error {
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        f.write_str(self.description())
+        match *self {
+            Error::Config(ref inner) => Display::fmt(inner, f),
+            Error::IdToken(ref inner) => Display::fmt(inner, f),
+            Error::Jwt(ref inner) => Display::fmt(inner, f),
+            Error::Seal(ref inner) => Display::fmt(inner, f),
+            _ => f.write_str(self.description()),
+        }
     }
 }
 
 impl StdError for Error {
     fn description(&self) -> &str {
         match *self {
+            Error::Api(_, ref inner) => &inner.message,
+            Error::Config(ref inner) => inner.description(),
             Error::Hyper(ref inner) => inner.description(),
+            Error::IdToken(ref inner) => inner.description(),
+            Error::InvalidPublicKey => "Invalid Ed25519 public key",
+            Error::InvalidSignature => "Invalid request signature",
             Error::Json(ref inner) => inner.description(),
+            Error::Jwt(ref inner) => inner.description(),
+            Error::MissingPermissions(ref inner) => &inner.message,
+            Error::MissingScope(ref inner) => &inner.message,
+            Error::Seal(ref inner) => inner.description(),
+            Error::StaleTimestamp => "Stale request timestamp",
+            Error::Status(ref status) => {
+                status.canonical_reason().unwrap_or("Unexpected status code")
+            },
+            Error::TokenRequest(ref inner) => &inner.error,
             Error::UrlEncode(ref inner) => inner.description(),
         }
     }
@@ -0,0 +1,219 @@
+//! Verification and parsing of the webhook events Discord sends to your
+//! application's webhook events URL.
+//!
+//! Every request is signed with your application's Ed25519 key. The signature
+//! is sent in the [`SIGNATURE_HEADER`] header and the timestamp it covers in
+//! the [`TIMESTAMP_HEADER`] header. Requests that fail verification must be
+//! rejected with a `401 Unauthorized` response. Requests whose timestamp is
+//! more than [`DEFAULT_MAX_AGE`] seconds away from the current time fail
+//! verification, so that captured requests can not be replayed.
+//!
+//! Verified requests, including pings, must be acknowledged with a
+//! [`ACKNOWLEDGE_STATUS`] response with an empty body.
+//!
+//! [`ACKNOWLEDGE_STATUS`]: constant.ACKNOWLEDGE_STATUS.html
+//! [`DEFAULT_MAX_AGE`]: constant.DEFAULT_MAX_AGE.html
+//! [`SIGNATURE_HEADER`]: constant.SIGNATURE_HEADER.html
+//! [`TIMESTAMP_HEADER`]: constant.TIMESTAMP_HEADER.html
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use hex;
+use hyper::status::StatusCode;
+use model::WebhookEventPayload;
+use serde_json;
+use super::{Error, Result};
+
+/// The status code to acknowledge a verified request with.
+pub const ACKNOWLEDGE_STATUS: StatusCode = StatusCode::NoContent;
+
+/// The number of seconds the timestamp of a request may be away from the
+/// current time by default.
+pub const DEFAULT_MAX_AGE: i64 = 300;
+
+/// The header containing the hex-encoded signature of a request.
+pub const SIGNATURE_HEADER: &str = "X-Signature-Ed25519";
+
+/// The header containing the timestamp covered by the signature of a request.
+pub const TIMESTAMP_HEADER: &str = "X-Signature-Timestamp";
+
+/// A verifier of the signatures of requests sent to your application's
+/// webhook events URL.
+///
+/// # Examples
+///
+/// Verify a request, and revoke the stored grant of a user that deauthorized
+/// your application:
+///
+/// ```rust,no_run
+/// # extern crate serenity_oauth;
+/// #
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// use serenity_oauth::events::EventVerifier;
+/// use serenity_oauth::model::WebhookEvent;
+/// use serenity_oauth::store::{MemoryTokenStore, TokenStore};
+///
+/// let store = MemoryTokenStore::new();
+/// let verifier = EventVerifier::new(
+///     "5f4f2b6b2f3c0d1e6a6f7e8d9c0b1a2f3e4d5c6b7a8f9e0d1c2b3a4f5e6d7c8b",
+/// )?;
+///
+/// # let (signature, timestamp, body) = ("", "", &b""[..]);
+/// #
+/// // Read from the request's headers and body.
+/// let payload = verifier.verify_and_parse(signature, timestamp, body)?;
+///
+/// if let Some(event) = payload.event {
+///     if let WebhookEvent::ApplicationDeauthorized(event) = event.parse()? {
+///         store.mark_revoked(&event.user.id.to_string());
+///     }
+/// }
+///
+/// // Respond with `events::ACKNOWLEDGE_STATUS`, for pings as well.
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct EventVerifier {
+    key: VerifyingKey,
+    max_age: i64,
+}
+
+impl EventVerifier {
+    /// Creates a verifier from your application's hex-encoded public key, as
+    /// shown in the developer portal.
+    ///
+    /// Returns [`Error::InvalidPublicKey`] if the key is not a valid Ed25519
+    /// public key.
+    ///
+    /// Requests are accepted for [`DEFAULT_MAX_AGE`] seconds unless another
+    /// maximum age is given with [`with_max_age`].
+    ///
+    /// [`DEFAULT_MAX_AGE`]: constant.DEFAULT_MAX_AGE.html
+    /// [`Error::InvalidPublicKey`]: ../enum.Error.html#variant.InvalidPublicKey
+    /// [`with_max_age`]: #method.with_max_age
+    pub fn new(public_key: &str) -> Result<Self> {
+        let mut bytes = [0; 32];
+
+        hex::decode_to_slice(public_key.trim(), &mut bytes)
+            .map_err(|_| Error::InvalidPublicKey)?;
+
+        VerifyingKey::from_bytes(&bytes)
+            .map(|key| Self {
+                key,
+                max_age: DEFAULT_MAX_AGE,
+            })
+            .map_err(|_| Error::InvalidPublicKey)
+    }
+
+    /// Accepts requests whose timestamp is at most the given number of seconds
+    /// away from the current time.
+    pub fn with_max_age(mut self, max_age: i64) -> Self {
+        self.max_age = max_age;
+
+        self
+    }
+
+    /// Verifies the signature of a request, given the values of its
+    /// [`SIGNATURE_HEADER`] and [`TIMESTAMP_HEADER`] headers and its raw body.
+    ///
+    /// Returns [`Error::InvalidSignature`] if verification fails, and
+    /// [`Error::StaleTimestamp`] if the timestamp is too far from the current
+    /// time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate ed25519_dalek;
+    /// extern crate hex;
+    /// extern crate serenity_oauth;
+    ///
+    /// use ed25519_dalek::{Signer, SigningKey};
+    /// use serenity_oauth::events::EventVerifier;
+    /// use std::time::{SystemTime, UNIX_EPOCH};
+    ///
+    /// # fn main() {
+    /// let signing_key = SigningKey::from_bytes(&[7; 32]);
+    /// let public_key = hex::encode(signing_key.verifying_key().as_bytes());
+    ///
+    /// let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    /// let timestamp = now.as_secs().to_string();
+    /// let body = br#"{"version":1,"application_id":"1","type":0}"#;
+    /// let message = [timestamp.as_bytes(), &body[..]].concat();
+    /// let signature = hex::encode(signing_key.sign(&message).to_bytes());
+    ///
+    /// let verifier = EventVerifier::new(&public_key).unwrap();
+    ///
+    /// assert!(verifier.verify(&signature, &timestamp, body).is_ok());
+    /// assert!(verifier.verify(&signature, "1700000001", body).is_err());
+    /// # }
+    /// ```
+    ///
+    /// [`Error::InvalidSignature`]: ../enum.Error.html#variant.InvalidSignature
+    /// [`Error::StaleTimestamp`]: ../enum.Error.html#variant.StaleTimestamp
+    /// [`SIGNATURE_HEADER`]: constant.SIGNATURE_HEADER.html
+    /// [`TIMESTAMP_HEADER`]: constant.TIMESTAMP_HEADER.html
+    pub fn verify(&self, signature: &str, timestamp: &str, body: &[u8])
+        -> Result<()> {
+        self.verify_at(signature, timestamp, body, Utc::now())
+    }
+
+    /// Verifies the signature of a request as of the given time.
+    ///
+    /// Refer to [`verify`] for more information.
+    ///
+    /// [`verify`]: #method.verify
+    pub fn verify_at(
+        &self,
+        signature: &str,
+        timestamp: &str,
+        body: &[u8],
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut bytes = [0; 64];
+
+        hex::decode_to_slice(signature.trim(), &mut bytes)
+            .map_err(|_| Error::InvalidSignature)?;
+
+        let mut message = Vec::with_capacity(timestamp.len() + body.len());
+        message.extend_from_slice(timestamp.as_bytes());
+        message.extend_from_slice(body);
+
+        self.key.verify(&message, &Signature::from_bytes(&bytes))
+            .map_err(|_| Error::InvalidSignature)?;
+
+        let sent_at = timestamp.trim()
+            .parse::<i64>()
+            .map_err(|_| Error::InvalidSignature)?;
+
+        let age = now.timestamp().saturating_sub(sent_at);
+
+        if age > self.max_age || age < -self.max_age {
+            return Err(Error::StaleTimestamp);
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the signature of a request, and then parses its body.
+    ///
+    /// Refer to [`verify`] for more information.
+    ///
+    /// [`verify`]: #method.verify
+    pub fn verify_and_parse(
+        &self,
+        signature: &str,
+        timestamp: &str,
+        body: &[u8],
+    ) -> Result<WebhookEventPayload> {
+        self.verify(signature, timestamp, body)?;
+
+        serde_json::from_slice(body).map_err(From::from)
+    }
+}
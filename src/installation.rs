@@ -0,0 +1,302 @@
+//! Bookkeeping of the guilds your bot was installed into through OAuth2.
+//!
+//! An [`InstallationRegistry`] records an [`Installation`] for every
+//! [`Grant`] that added your bot to a guild, and reports which of the
+//! permissions requested by your bot authorization URL were not granted.
+//!
+//! The [`InstallationStore`] trait can be implemented on top of any storage
+//! backend, such as a database. A simple in-memory implementation is provided
+//! as [`MemoryInstallationStore`].
+//!
+//! [`Grant`]: ../model/struct.Grant.html
+//! [`Installation`]: struct.Installation.html
+//! [`InstallationRegistry`]: struct.InstallationRegistry.html
+//! [`InstallationStore`]: trait.InstallationStore.html
+//! [`MemoryInstallationStore`]: struct.MemoryInstallationStore.html
+
+use model::Grant;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::SystemTime;
+use utils::Permissions;
+
+/// A record of your bot being installed into a guild.
+#[derive(Clone, Debug)]
+pub struct Installation {
+    /// The ID of the guild the bot was installed into.
+    pub guild_id: u64,
+    /// The name of the guild at the time of installation.
+    pub guild_name: String,
+    /// The time at which the bot was installed.
+    pub installed_at: SystemTime,
+    /// The ID of the user that installed the bot, if known.
+    pub installer_id: Option<u64>,
+    /// The permissions granted to the bot, if known.
+    pub permissions: Option<Permissions>,
+}
+
+impl Installation {
+    /// Creates an installation from a grant, if the grant added the bot to a
+    /// guild.
+    ///
+    /// The permissions are taken from [`Grant::permissions`]. If they were not
+    /// set from the redirect, they are recorded as unknown.
+    ///
+    /// [`Grant::permissions`]: ../model/struct.Grant.html#structfield.permissions
+    pub fn from_grant(grant: &Grant, installer_id: Option<u64>)
+        -> Option<Self> {
+        grant.guild.as_ref().map(|guild| Self {
+            guild_id: guild.id.0,
+            guild_name: guild.name.clone(),
+            installed_at: SystemTime::now(),
+            permissions: grant.permissions,
+            installer_id,
+        })
+    }
+
+    /// The permissions out of the given required permissions that were not
+    /// granted to the bot, or `None` if the granted permissions are unknown.
+    ///
+    /// No permissions are missing if the bot was granted the administrator
+    /// permission.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_oauth::installation::Installation;
+    /// use serenity_oauth::utils::Permissions;
+    /// use std::time::SystemTime;
+    ///
+    /// let installation = Installation {
+    ///     guild_id: 381880193251409931,
+    ///     guild_name: "My Guild".to_owned(),
+    ///     installed_at: SystemTime::now(),
+    ///     installer_id: None,
+    ///     permissions: Some(Permissions::SEND_MESSAGES),
+    /// };
+    ///
+    /// let required = Permissions::ADD_REACTIONS | Permissions::SEND_MESSAGES;
+    ///
+    /// assert_eq!(
+    ///     installation.missing_permissions(required),
+    ///     Some(Permissions::ADD_REACTIONS),
+    /// );
+    /// ```
+    pub fn missing_permissions(&self, required: Permissions)
+        -> Option<Permissions> {
+        self.permissions.map(|permissions| {
+            if permissions.contains(Permissions::ADMINISTRATOR) {
+                Permissions::empty()
+            } else {
+                required - permissions
+            }
+        })
+    }
+}
+
+/// A trait for storing installations, keyed by the guild's ID.
+pub trait InstallationStore {
+    /// Retrieves every stored installation.
+    fn all(&self) -> Vec<Installation>;
+
+    /// Retrieves the installation into the given guild, if there is one.
+    fn get(&self, guild_id: u64) -> Option<Installation>;
+
+    /// Stores an installation, replacing any previous installation into the
+    /// same guild.
+    fn insert(&self, installation: Installation);
+
+    /// Removes the installation into the given guild, returning it if there
+    /// was one.
+    fn remove(&self, guild_id: u64) -> Option<Installation>;
+}
+
+/// An [`InstallationStore`] that keeps installations in memory.
+///
+/// [`InstallationStore`]: trait.InstallationStore.html
+#[derive(Debug, Default)]
+pub struct MemoryInstallationStore {
+    installations: RwLock<HashMap<u64, Installation>>,
+}
+
+impl MemoryInstallationStore {
+    /// Creates a new, empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl InstallationStore for MemoryInstallationStore {
+    fn all(&self) -> Vec<Installation> {
+        self.installations.read().unwrap().values().cloned().collect()
+    }
+
+    fn get(&self, guild_id: u64) -> Option<Installation> {
+        self.installations.read().unwrap().get(&guild_id).cloned()
+    }
+
+    fn insert(&self, installation: Installation) {
+        self.installations
+            .write()
+            .unwrap()
+            .insert(installation.guild_id, installation);
+    }
+
+    fn remove(&self, guild_id: u64) -> Option<Installation> {
+        self.installations.write().unwrap().remove(&guild_id)
+    }
+}
+
+/// A registry of the guilds your bot was installed into, checking the granted
+/// permissions against the permissions your bot requests.
+///
+/// # Examples
+///
+/// Record the installation from a bot authorization, and check whether any
+/// requested permissions were unchecked by the installing user:
+///
+/// ```rust,no_run
+/// extern crate hyper;
+/// extern crate serenity_oauth;
+///
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// use hyper::Client;
+/// use serenity_oauth::installation::{
+///     InstallationRegistry,
+///     MemoryInstallationStore,
+/// };
+/// use serenity_oauth::utils::Permissions;
+/// use serenity_oauth::{DiscordOAuthHyperRequester, OAuthClient};
+///
+/// let requested = Permissions::ADD_REACTIONS | Permissions::SEND_MESSAGES;
+/// let registry = InstallationRegistry::new(
+///     MemoryInstallationStore::new(),
+///     requested,
+/// );
+///
+/// let oauth = OAuthClient::new(
+///     249608697955745802,
+///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+///     "https://myapplication.website",
+/// );
+/// let requester = oauth.requester(Client::new());
+///
+/// // Redirect the user to this URL, and receive the code and permissions
+/// // query parameters in the redirect.
+/// let url = oauth.bot_authorization_url(requested);
+///
+/// # let permissions_param = "2048";
+/// let request = oauth.code_exchange_request("user code here");
+/// let mut grant = requester.exchange_grant(&request)?;
+/// grant.permissions = permissions_param.parse()
+///     .ok()
+///     .map(Permissions::from_bits_truncate);
+///
+/// let installer = requester.current_user(&grant.access_token)?;
+///
+/// if let Some(installation) = registry.record(&grant, Some(installer.id)) {
+///     let missing = registry.missing_permissions(installation.guild_id);
+///
+///     if let Some(missing) = missing.filter(|missing| !missing.is_empty()) {
+///         println!("Missing permissions: {:?}", missing);
+///     }
+/// }
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct InstallationRegistry<S: InstallationStore> {
+    requested: Permissions,
+    store: S,
+}
+
+impl<S: InstallationStore> InstallationRegistry<S> {
+    /// Creates a registry backed by the given store, checking installations
+    /// against the given requested permissions.
+    ///
+    /// These should be the permissions passed to
+    /// [`utils::bot_authorization_url`].
+    ///
+    /// [`utils::bot_authorization_url`]: ../utils/fn.bot_authorization_url.html
+    pub fn new(store: S, requested: Permissions) -> Self {
+        Self {
+            requested,
+            store,
+        }
+    }
+
+    /// The permissions that installations are checked against.
+    pub fn requested(&self) -> Permissions {
+        self.requested
+    }
+
+    /// The store backing the registry.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Records the installation from a grant, if the grant added the bot to a
+    /// guild.
+    ///
+    /// Refer to [`Installation::from_grant`] for how the installation is
+    /// created.
+    ///
+    /// [`Installation::from_grant`]: struct.Installation.html#method.from_grant
+    pub fn record(&self, grant: &Grant, installer_id: Option<u64>)
+        -> Option<Installation> {
+        let installation = Installation::from_grant(grant, installer_id)?;
+        self.store.insert(installation.clone());
+
+        Some(installation)
+    }
+
+    /// Retrieves the installation into the given guild, if there is one.
+    pub fn get(&self, guild_id: u64) -> Option<Installation> {
+        self.store.get(guild_id)
+    }
+
+    /// Retrieves every recorded installation.
+    pub fn installations(&self) -> Vec<Installation> {
+        self.store.all()
+    }
+
+    /// Removes the installation into the given guild, such as when the bot
+    /// was removed from it.
+    pub fn remove(&self, guild_id: u64) -> Option<Installation> {
+        self.store.remove(guild_id)
+    }
+
+    /// The requested permissions that were not granted in the given guild, or
+    /// `None` if there is no installation into it or its granted permissions
+    /// are unknown.
+    pub fn missing_permissions(&self, guild_id: u64) -> Option<Permissions> {
+        self.store.get(guild_id).and_then(|installation| {
+            installation.missing_permissions(self.requested)
+        })
+    }
+
+    /// Every installation that is missing requested permissions, along with
+    /// the permissions that are missing.
+    ///
+    /// Installations whose granted permissions are unknown are left out.
+    pub fn shortfalls(&self) -> Vec<(Installation, Permissions)> {
+        self.store.all()
+            .into_iter()
+            .filter_map(|installation| {
+                let missing = installation.missing_permissions(self.requested)?;
+
+                if missing.is_empty() {
+                    None
+                } else {
+                    Some((installation, missing))
+                }
+            })
+            .collect()
+    }
+}
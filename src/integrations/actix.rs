@@ -0,0 +1,431 @@
+//! Integration with the [actix-web] web framework.
+//!
+//! [`DiscordAuth::configure`] registers routes for logging users in and out,
+//! the [`DiscordUser`] and [`GrantedScopes`] extractors give handlers access to
+//! the logged in user, and the [`RequireScopes`] guard only matches routes for
+//! users that granted particular scopes.
+//!
+//! The logged in user and their granted scopes are kept in a cookie, sealed by
+//! the [`SessionSealer`] given to the [`DiscordAuth`]. Their grant is kept in a
+//! [`TokenStore`] keyed by their ID, so that the access token never reaches the
+//! browser.
+//!
+//! # Examples
+//!
+//! Greet the logged in user, and list their guilds if they granted the
+//! [`Scope::Guilds`] scope:
+//!
+//! ```rust,ignore
+//! extern crate actix_web;
+//! extern crate hyper;
+//! extern crate hyper_native_tls;
+//! extern crate serenity_oauth;
+//!
+//! use actix_web::{web, App, HttpServer};
+//! use hyper::net::HttpsConnector;
+//! use hyper::Client;
+//! use hyper_native_tls::NativeTlsClient;
+//! use serenity_oauth::integrations::actix::{
+//!     DiscordAuth,
+//!     DiscordUser,
+//!     RequireScopes,
+//! };
+//! use serenity_oauth::seal::{SealingKey, SessionSealer};
+//! use serenity_oauth::{OAuthClient, Scope};
+//!
+//! async fn index(user: DiscordUser) -> String {
+//!     format!("Hello, {}!", user.0.username)
+//! }
+//!
+//! async fn guilds(user: DiscordUser) -> String {
+//!     format!("Managing the guilds of {}.", user.0.username)
+//! }
+//!
+//! #[actix_web::main]
+//! async fn main() -> std::io::Result<()> {
+//!     let oauth = OAuthClient::new(
+//!         249608697955745802,
+//!         "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+//!         "http://localhost:8080/auth/callback",
+//!     );
+//!     let connector = HttpsConnector::new(NativeTlsClient::new().unwrap());
+//!     let client = Client::with_connector(connector);
+//!     let sealer = SessionSealer::new(SealingKey::generate());
+//!     let auth = DiscordAuth::new(oauth, client, sealer)
+//!         .with_scopes(vec![Scope::Identify, Scope::Guilds])
+//!         .with_secure_cookies(false);
+//!
+//!     HttpServer::new(move || {
+//!         App::new()
+//!             .configure(|config| auth.configure(config))
+//!             .route("/", web::get().to(index))
+//!             .route(
+//!                 "/guilds",
+//!                 web::get()
+//!                     .guard(RequireScopes::new(vec![Scope::Guilds]))
+//!                     .to(guilds),
+//!             )
+//!     }).bind(("127.0.0.1", 8080))?.run().await
+//! }
+//! ```
+//!
+//! [actix-web]: https://actix.rs
+//! [`DiscordAuth`]: struct.DiscordAuth.html
+//! [`DiscordAuth::configure`]: struct.DiscordAuth.html#method.configure
+//! [`DiscordUser`]: struct.DiscordUser.html
+//! [`GrantedScopes`]: struct.GrantedScopes.html
+//! [`RequireScopes`]: struct.RequireScopes.html
+//! [`Scope::Guilds`]: ../../enum.Scope.html#variant.Guilds
+//! [`SessionSealer`]: ../../seal/struct.SessionSealer.html
+//! [`TokenStore`]: ../../store/trait.TokenStore.html
+
+use actix_web::dev::Payload;
+use actix_web::error::{ErrorInternalServerError, InternalError};
+use actix_web::guard::{Guard, GuardContext};
+use actix_web::http::header::{
+    HeaderMap,
+    HeaderValue,
+    COOKIE,
+    LOCATION,
+    SET_COOKIE,
+};
+use actix_web::web::{self, Data, Query, ServiceConfig};
+use actix_web::{
+    Error as ActixError,
+    FromRequest,
+    HttpRequest,
+    HttpResponse,
+    HttpResponseBuilder,
+};
+use bridge::hyper::HyperRequester;
+use futures_util::FutureExt;
+use hyper::client::Client as HyperClient;
+use model::CurrentUser;
+use seal::SessionSealer;
+use std::collections::HashSet;
+use std::future::{self, Future, Ready};
+use std::result::Result as StdResult;
+use std::sync::Arc;
+use store::{MemoryTokenStore, TokenStore};
+use super::{
+    complete_login,
+    cookie_value,
+    generate_state,
+    open_session,
+    removal_cookie,
+    seal_session,
+    session_cookie,
+    state_cookie,
+    CallbackQuery,
+    LoginError,
+    SESSION_COOKIE,
+    STATE_COOKIE,
+};
+use ::{OAuthClient, Scope};
+
+/// The configuration of the login routes, extractors and guards.
+///
+/// The following routes are registered by [`configure`] under the [`base`]
+/// path, which defaults to `/auth`:
+///
+/// - `GET /login`: redirects to Discord's authorization page;
+/// - `GET /callback`: completes the login, and redirects to [`after_login`];
+/// - `POST /logout`: ends the session, and redirects to [`after_logout`].
+///
+/// Logging out requires a `POST` request, so that other sites can not log
+/// users out by linking to the route.
+///
+/// The default redirect URI of the [`OAuthClient`] must point to the callback
+/// route, and the scopes requested must include [`Scope::Identify`].
+///
+/// [`OAuthClient`]: ../../struct.OAuthClient.html
+/// [`Scope::Identify`]: ../../enum.Scope.html#variant.Identify
+/// [`after_login`]: #structfield.after_login
+/// [`after_logout`]: #structfield.after_logout
+/// [`base`]: #structfield.base
+/// [`configure`]: #method.configure
+#[derive(Clone)]
+pub struct DiscordAuth {
+    /// The path users are redirected to after logging in.
+    ///
+    /// This defaults to `/`.
+    pub after_login: String,
+    /// The path users are redirected to after logging out.
+    ///
+    /// This defaults to `/`.
+    pub after_logout: String,
+    /// The path the routes are registered under.
+    pub base: String,
+    oauth: OAuthClient,
+    requester: Arc<HyperRequester>,
+    sealer: SessionSealer,
+    secure_cookies: bool,
+    store: Arc<dyn TokenStore + Send + Sync>,
+}
+
+impl DiscordAuth {
+    /// Creates the configuration from your application's configuration, a
+    /// hyper Client able to make HTTPS requests, and the sealer to seal cookies
+    /// with.
+    ///
+    /// Users are asked for the default scopes of the [`OAuthClient`] unless
+    /// others are given with [`with_scopes`], and their grants are kept in a
+    /// [`MemoryTokenStore`] unless another store is given with [`with_store`].
+    ///
+    /// [`MemoryTokenStore`]: ../../store/struct.MemoryTokenStore.html
+    /// [`OAuthClient`]: ../../struct.OAuthClient.html
+    /// [`with_scopes`]: #method.with_scopes
+    /// [`with_store`]: #method.with_store
+    pub fn new(
+        oauth: OAuthClient,
+        client: HyperClient,
+        sealer: SessionSealer,
+    ) -> Self {
+        Self {
+            after_login: "/".to_owned(),
+            after_logout: "/".to_owned(),
+            base: "/auth".to_owned(),
+            requester: Arc::new(oauth.requester(client)),
+            oauth,
+            sealer,
+            secure_cookies: true,
+            store: Arc::new(MemoryTokenStore::new()),
+        }
+    }
+
+    /// Asks users for the given scopes when they log in.
+    ///
+    /// The scopes must include [`Scope::Identify`].
+    ///
+    /// [`Scope::Identify`]: ../../enum.Scope.html#variant.Identify
+    pub fn with_scopes(mut self, scopes: Vec<Scope>) -> Self {
+        self.oauth.scopes = scopes;
+
+        self
+    }
+
+    /// Sets whether cookies are only sent over HTTPS.
+    ///
+    /// This defaults to `true`, and should only be turned off for local
+    /// development over plain HTTP.
+    pub fn with_secure_cookies(mut self, secure: bool) -> Self {
+        self.secure_cookies = secure;
+
+        self
+    }
+
+    /// Keeps users' grants in the given store.
+    pub fn with_store<S>(mut self, store: S) -> Self
+        where S: TokenStore + Send + Sync + 'static {
+        self.store = Arc::new(store);
+
+        self
+    }
+
+    /// Registers the login, callback, and logout routes, along with the data
+    /// the extractors and guards rely on.
+    ///
+    /// This is meant to be passed to `App::configure`.
+    pub fn configure(&self, config: &mut ServiceConfig) {
+        config.app_data(Data::new(self.clone())).service(
+            web::scope(&self.base)
+                .route("/login", web::get().to(login))
+                .route("/callback", web::get().to(callback))
+                .route("/logout", web::post().to(logout)),
+        );
+    }
+
+    fn identity(&self, headers: &HeaderMap) -> Option<Identity> {
+        let value = read_cookie(headers, SESSION_COOKIE)?;
+        let (user, scopes) = open_session(&self.sealer, value)?;
+
+        Some(Identity {
+            scopes,
+            user,
+        })
+    }
+
+    fn login_redirect(&self) -> HttpResponse {
+        redirect(&format!("{}/login", self.base)).finish()
+    }
+}
+
+/// An extractor yielding the logged in user.
+///
+/// The request is redirected to the login route if no user is logged in.
+#[derive(Clone, Debug)]
+pub struct DiscordUser(pub CurrentUser);
+
+impl FromRequest for DiscordUser {
+    type Error = ActixError;
+    type Future = Ready<StdResult<Self, ActixError>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        future::ready(identify(request).map(|identity| {
+            DiscordUser(identity.user)
+        }))
+    }
+}
+
+/// An extractor yielding the scopes the logged in user granted.
+///
+/// The request is redirected to the login route if no user is logged in.
+#[derive(Clone, Debug)]
+pub struct GrantedScopes(pub HashSet<Scope>);
+
+impl GrantedScopes {
+    /// Whether the given scope was granted.
+    pub fn contains(&self, scope: &Scope) -> bool {
+        self.0.contains(scope)
+    }
+}
+
+impl FromRequest for GrantedScopes {
+    type Error = ActixError;
+    type Future = Ready<StdResult<Self, ActixError>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        future::ready(identify(request).map(|identity| {
+            GrantedScopes(identity.scopes.into_iter().collect())
+        }))
+    }
+}
+
+/// A guard matching requests of logged in users that granted all of the given
+/// scopes.
+///
+/// Requests that do not match fall through to the following routes, ending in
+/// a `404 Not Found` response if there are none.
+#[derive(Clone, Debug)]
+pub struct RequireScopes {
+    scopes: Vec<Scope>,
+}
+
+impl RequireScopes {
+    /// Creates a guard requiring the given scopes.
+    pub fn new(scopes: Vec<Scope>) -> Self {
+        Self {
+            scopes,
+        }
+    }
+}
+
+impl Guard for RequireScopes {
+    fn check(&self, context: &GuardContext) -> bool {
+        let auth = match context.app_data::<Data<DiscordAuth>>() {
+            Some(auth) => auth,
+            None => return false,
+        };
+
+        auth.identity(context.head().headers()).map_or(false, |identity| {
+            self.scopes.iter().all(|scope| identity.scopes.contains(scope))
+        })
+    }
+}
+
+/// The logged in user, as kept in the session cookie.
+struct Identity {
+    scopes: Vec<Scope>,
+    user: CurrentUser,
+}
+
+fn login(auth: Data<DiscordAuth>) -> impl Future<Output = HttpResponse> {
+    let csrf = generate_state();
+    let url = auth.oauth.authorization_url(Some(&csrf));
+
+    future::ready(match auth.sealer.seal_value(STATE_COOKIE, &csrf) {
+        Ok(value) => {
+            let cookie = state_cookie(&value, auth.secure_cookies);
+
+            redirect(&url).append_header((SET_COOKIE, cookie)).finish()
+        },
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    })
+}
+
+fn callback(
+    auth: Data<DiscordAuth>,
+    request: HttpRequest,
+    query: Query<CallbackQuery>,
+) -> impl Future<Output = HttpResponse> {
+    let expected = read_cookie(request.headers(), STATE_COOKIE)
+        .and_then(|value| {
+            auth.sealer.open_value::<String>(STATE_COOKIE, value).ok()
+        });
+    let blocking = Data::clone(&auth);
+
+    let future = web::block(move || {
+        let session = complete_login(
+            &blocking.oauth,
+            &*blocking.requester,
+            &query,
+            expected.as_ref().map(|state| &**state),
+        )?;
+
+        blocking.store.insert(&session.user.id.to_string(), (&session).into());
+
+        Ok(seal_session(&blocking.sealer, session))
+    });
+
+    future.map(move |result: StdResult<StdResult<_, LoginError>, _>| {
+        let mut response = match result {
+            Ok(Ok(Ok(value))) => {
+                let cookie = session_cookie(&value, auth.secure_cookies);
+
+                redirect(&auth.after_login)
+                    .append_header((SET_COOKIE, cookie))
+                    .finish()
+            },
+            Ok(Ok(Err(_))) => HttpResponse::InternalServerError().finish(),
+            Ok(Err(LoginError::Denied(_))) => {
+                HttpResponse::Forbidden().finish()
+            },
+            Ok(Err(LoginError::Request(_))) => {
+                HttpResponse::BadGateway().finish()
+            },
+            Ok(Err(_)) => HttpResponse::BadRequest().finish(),
+            Err(_) => HttpResponse::InternalServerError().finish(),
+        };
+
+        let removal = removal_cookie(STATE_COOKIE);
+
+        if let Ok(value) = HeaderValue::from_str(&removal) {
+            response.headers_mut().append(SET_COOKIE, value);
+        }
+
+        response
+    })
+}
+
+fn logout(auth: Data<DiscordAuth>) -> impl Future<Output = HttpResponse> {
+    future::ready(redirect(&auth.after_logout)
+        .append_header((SET_COOKIE, removal_cookie(SESSION_COOKIE)))
+        .finish())
+}
+
+/// Retrieves the logged in user from a request, or an error redirecting to the
+/// login route.
+fn identify(request: &HttpRequest) -> StdResult<Identity, ActixError> {
+    let auth = request.app_data::<Data<DiscordAuth>>()
+        .ok_or_else(|| ErrorInternalServerError("DiscordAuth not configured"))?;
+
+    auth.identity(request.headers()).ok_or_else(|| {
+        InternalError::from_response("Not logged in", auth.login_redirect())
+            .into()
+    })
+}
+
+/// Finds the value of a cookie in the headers of a request.
+fn read_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    let values = headers.get_all(COOKIE)
+        .filter_map(|header| header.to_str().ok());
+
+    cookie_value(values, name)
+}
+
+fn redirect(location: &str) -> HttpResponseBuilder {
+    let mut builder = HttpResponse::Found();
+    builder.insert_header((LOCATION, location));
+
+    builder
+}
@@ -0,0 +1,349 @@
+//! Integration with the [axum] web framework.
+//!
+//! [`DiscordAuth::router`] creates routes for logging users in and out, and
+//! the [`DiscordUser`] and [`DiscordAccessToken`] extractors give handlers
+//! access to the logged in user.
+//!
+//! The logged in user is kept in a cookie, sealed by the [`SessionSealer`]
+//! given to the [`DiscordAuth`]. Their grant is kept in a [`TokenStore`] keyed
+//! by their ID, so that the access token never reaches the browser.
+//!
+//! Requests to Discord are made on tokio's blocking thread pool.
+//!
+//! # Examples
+//!
+//! Greet the logged in user, redirecting to the login route otherwise:
+//!
+//! ```rust,ignore
+//! extern crate axum;
+//! extern crate hyper;
+//! extern crate hyper_native_tls;
+//! extern crate serenity_oauth;
+//!
+//! use axum::routing::get;
+//! use axum::Router;
+//! use hyper::net::HttpsConnector;
+//! use hyper::Client;
+//! use hyper_native_tls::NativeTlsClient;
+//! use serenity_oauth::integrations::axum::{DiscordAuth, DiscordUser};
+//! use serenity_oauth::seal::{SealingKey, SessionSealer};
+//! use serenity_oauth::OAuthClient;
+//!
+//! async fn index(user: DiscordUser) -> String {
+//!     format!("Hello, {}!", user.0.username)
+//! }
+//!
+//! fn app() -> Router {
+//!     let oauth = OAuthClient::new(
+//!         249608697955745802,
+//!         "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+//!         "http://localhost:3000/auth/callback",
+//!     );
+//!     let connector = HttpsConnector::new(NativeTlsClient::new().unwrap());
+//!     let client = Client::with_connector(connector);
+//!     let sealer = SessionSealer::new(SealingKey::generate());
+//!     let auth = DiscordAuth::new(oauth, client, sealer)
+//!         .with_secure_cookies(false);
+//!
+//!     Router::new()
+//!         .route("/", get(index))
+//!         .merge(auth.router())
+//!         .with_state(auth)
+//! }
+//! ```
+//!
+//! [axum]: https://github.com/tokio-rs/axum
+//! [`DiscordAccessToken`]: struct.DiscordAccessToken.html
+//! [`DiscordAuth`]: struct.DiscordAuth.html
+//! [`DiscordAuth::router`]: struct.DiscordAuth.html#method.router
+//! [`DiscordUser`]: struct.DiscordUser.html
+//! [`SessionSealer`]: ../../seal/struct.SessionSealer.html
+//! [`TokenStore`]: ../../store/trait.TokenStore.html
+
+use axum::extract::{FromRef, FromRequestParts, Query, State};
+use axum::http::header::{HeaderMap, COOKIE, SET_COOKIE};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{AppendHeaders, IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use bridge::hyper::HyperRequester;
+use futures_util::future::Either;
+use futures_util::FutureExt;
+use hyper::client::Client as HyperClient;
+use model::CurrentUser;
+use seal::SessionSealer;
+use std::future::{self, Future};
+use std::result::Result as StdResult;
+use std::sync::Arc;
+use store::{MemoryTokenStore, TokenStore};
+use super::{
+    complete_login,
+    cookie_value,
+    fresh_access_token,
+    generate_state,
+    open_session,
+    removal_cookie,
+    seal_session,
+    session_cookie,
+    state_cookie,
+    CallbackQuery,
+    LoginError,
+    SESSION_COOKIE,
+    STATE_COOKIE,
+};
+use tokio::task;
+use ::OAuthClient;
+
+/// The state behind the login routes and extractors.
+///
+/// The following routes are created by [`router`] under the [`base`] path,
+/// which defaults to `/auth`:
+///
+/// - `GET /login`: redirects to Discord's authorization page;
+/// - `GET /callback`: completes the login, and redirects to [`after_login`];
+/// - `POST /logout`: ends the session, and redirects to [`after_logout`].
+///
+/// Logging out requires a `POST` request, so that other sites can not log
+/// users out by linking to the route.
+///
+/// The default redirect URI of the [`OAuthClient`] must point to the callback
+/// route, and its scopes must include [`Scope::Identify`].
+///
+/// The extractors require the state of your router to provide a `DiscordAuth`
+/// through axum's `FromRef`, which is the case if it is the state itself.
+///
+/// [`OAuthClient`]: ../../struct.OAuthClient.html
+/// [`Scope::Identify`]: ../../enum.Scope.html#variant.Identify
+/// [`after_login`]: #structfield.after_login
+/// [`after_logout`]: #structfield.after_logout
+/// [`base`]: #structfield.base
+/// [`router`]: #method.router
+#[derive(Clone)]
+pub struct DiscordAuth {
+    /// The path users are redirected to after logging in.
+    ///
+    /// This defaults to `/`.
+    pub after_login: String,
+    /// The path users are redirected to after logging out.
+    ///
+    /// This defaults to `/`.
+    pub after_logout: String,
+    /// The path the routes are created under.
+    pub base: String,
+    oauth: OAuthClient,
+    requester: Arc<HyperRequester>,
+    sealer: SessionSealer,
+    secure_cookies: bool,
+    store: Arc<dyn TokenStore + Send + Sync>,
+}
+
+impl DiscordAuth {
+    /// Creates the state from your application's configuration, a hyper Client
+    /// able to make HTTPS requests, and the sealer to seal cookies with.
+    ///
+    /// Users' grants are kept in a [`MemoryTokenStore`] unless another store is
+    /// given with [`with_store`].
+    ///
+    /// [`MemoryTokenStore`]: ../../store/struct.MemoryTokenStore.html
+    /// [`with_store`]: #method.with_store
+    pub fn new(
+        oauth: OAuthClient,
+        client: HyperClient,
+        sealer: SessionSealer,
+    ) -> Self {
+        Self {
+            after_login: "/".to_owned(),
+            after_logout: "/".to_owned(),
+            base: "/auth".to_owned(),
+            requester: Arc::new(oauth.requester(client)),
+            oauth,
+            sealer,
+            secure_cookies: true,
+            store: Arc::new(MemoryTokenStore::new()),
+        }
+    }
+
+    /// Sets whether cookies are only sent over HTTPS.
+    ///
+    /// This defaults to `true`, and should only be turned off for local
+    /// development over plain HTTP.
+    pub fn with_secure_cookies(mut self, secure: bool) -> Self {
+        self.secure_cookies = secure;
+
+        self
+    }
+
+    /// Keeps users' grants in the given store.
+    pub fn with_store<S>(mut self, store: S) -> Self
+        where S: TokenStore + Send + Sync + 'static {
+        self.store = Arc::new(store);
+
+        self
+    }
+
+    /// Creates a router with the login, callback, and logout routes, to be
+    /// merged into your application's router.
+    pub fn router<S>(&self) -> Router<S>
+        where S: Clone + Send + Sync + 'static {
+        Router::new()
+            .route(&format!("{}/login", self.base), get(login))
+            .route(&format!("{}/callback", self.base), get(callback))
+            .route(&format!("{}/logout", self.base), post(logout))
+            .with_state(self.clone())
+    }
+
+    fn current_user(&self, parts: &Parts) -> Option<CurrentUser> {
+        let value = read_cookie(&parts.headers, SESSION_COOKIE)?;
+
+        open_session(&self.sealer, value).map(|(user, _)| user)
+    }
+
+    fn login_redirect(&self) -> Redirect {
+        Redirect::to(&format!("{}/login", self.base))
+    }
+}
+
+/// An extractor yielding the logged in user.
+///
+/// The request is redirected to the login route if no user is logged in.
+#[derive(Clone, Debug)]
+pub struct DiscordUser(pub CurrentUser);
+
+impl<S> FromRequestParts<S> for DiscordUser
+    where DiscordAuth: FromRef<S>, S: Send + Sync {
+    type Rejection = Redirect;
+
+    fn from_request_parts(parts: &mut Parts, state: &S)
+        -> impl Future<Output = StdResult<Self, Redirect>> + Send {
+        let auth = DiscordAuth::from_ref(state);
+
+        future::ready(match auth.current_user(parts) {
+            Some(user) => Ok(DiscordUser(user)),
+            None => Err(auth.login_redirect()),
+        })
+    }
+}
+
+/// An extractor yielding an access token of the logged in user that has not
+/// expired.
+///
+/// If the stored access token has expired, it is refreshed first. The request
+/// is redirected to the login route if no user is logged in or their grant was
+/// revoked, and fails with a `502 Bad Gateway` status if the refresh fails
+/// otherwise.
+#[derive(Clone, Debug)]
+pub struct DiscordAccessToken(pub String);
+
+impl<S> FromRequestParts<S> for DiscordAccessToken
+    where DiscordAuth: FromRef<S>, S: Send + Sync {
+    type Rejection = Response;
+
+    fn from_request_parts(parts: &mut Parts, state: &S)
+        -> impl Future<Output = StdResult<Self, Response>> + Send {
+        let auth = DiscordAuth::from_ref(state);
+        let login = auth.login_redirect();
+
+        let user_id = match auth.current_user(parts) {
+            Some(user) => user.id,
+            None => return Either::Left(future::ready(
+                Err(login.into_response()),
+            )),
+        };
+
+        let future = task::spawn_blocking(move || {
+            fresh_access_token(
+                &auth.oauth,
+                &*auth.requester,
+                &auth.store,
+                user_id,
+            )
+        });
+
+        Either::Right(future.map(move |result| match result {
+            Ok(Ok(Some(token))) => Ok(DiscordAccessToken(token)),
+            Ok(Ok(None)) => Err(login.into_response()),
+            Ok(Err(_)) => Err(StatusCode::BAD_GATEWAY.into_response()),
+            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR.into_response()),
+        }))
+    }
+}
+
+fn login(State(auth): State<DiscordAuth>) -> impl Future<Output = Response> {
+    let csrf = generate_state();
+    let url = auth.oauth.authorization_url(Some(&csrf));
+
+    future::ready(match auth.sealer.seal_value(STATE_COOKIE, &csrf) {
+        Ok(value) => {
+            let cookie = state_cookie(&value, auth.secure_cookies);
+
+            (AppendHeaders([(SET_COOKIE, cookie)]), Redirect::to(&url))
+                .into_response()
+        },
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    })
+}
+
+fn callback(
+    State(auth): State<DiscordAuth>,
+    headers: HeaderMap,
+    Query(query): Query<CallbackQuery>,
+) -> impl Future<Output = Response> {
+    let expected = read_cookie(&headers, STATE_COOKIE)
+        .and_then(|value| {
+            auth.sealer.open_value::<String>(STATE_COOKIE, value).ok()
+        });
+    let removal = AppendHeaders([(SET_COOKIE, removal_cookie(STATE_COOKIE))]);
+    let after_login = auth.after_login.clone();
+    let secure = auth.secure_cookies;
+
+    let future = task::spawn_blocking(move || {
+        let session = complete_login(
+            &auth.oauth,
+            &*auth.requester,
+            &query,
+            expected.as_ref().map(|state| &**state),
+        )?;
+
+        auth.store.insert(&session.user.id.to_string(), (&session).into());
+
+        Ok(seal_session(&auth.sealer, session))
+    });
+
+    future.map(move |result: StdResult<StdResult<_, LoginError>, _>| {
+        match result {
+            Ok(Ok(Ok(value))) => (
+                removal,
+                AppendHeaders([(SET_COOKIE, session_cookie(&value, secure))]),
+                Redirect::to(&after_login),
+            ).into_response(),
+            Ok(Ok(Err(_))) => {
+                (removal, StatusCode::INTERNAL_SERVER_ERROR).into_response()
+            },
+            Ok(Err(LoginError::Denied(_))) => {
+                (removal, StatusCode::FORBIDDEN).into_response()
+            },
+            Ok(Err(LoginError::Request(_))) => {
+                (removal, StatusCode::BAD_GATEWAY).into_response()
+            },
+            Ok(Err(_)) => (removal, StatusCode::BAD_REQUEST).into_response(),
+            Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    })
+}
+
+fn logout(State(auth): State<DiscordAuth>) -> impl Future<Output = Response> {
+    future::ready((
+        AppendHeaders([(SET_COOKIE, removal_cookie(SESSION_COOKIE))]),
+        Redirect::to(&auth.after_logout),
+    ).into_response())
+}
+
+/// Finds the value of a cookie in the headers of a request.
+fn read_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    let values = headers.get_all(COOKIE)
+        .iter()
+        .filter_map(|header| header.to_str().ok());
+
+    cookie_value(values, name)
+}
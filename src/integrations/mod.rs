@@ -0,0 +1,397 @@
+//! Integrations with web frameworks, handling the login flow of your users.
+//!
+//! Each integration is enabled by the feature of the same name:
+//!
+//! - `actix`: a service, extractors and guards for [actix-web];
+//! - `axum`: a router and extractors for [axum];
+//! - `rocket`: a fairing and request guards for [Rocket];
+//! - `tower`: a middleware layer for any server built on [tower].
+//!
+//! The integrations share the [`Session`] kept for a logged in user, and the
+//! handling of the redirect back from Discord in [`complete_login`]. Each keeps
+//! the logged in user in a [`SealedSession`] cookie, sealed by the
+//! [`SessionSealer`] given to it, and their grant in a [`TokenStore`].
+//!
+//! [Rocket]: https://rocket.rs
+//! [actix-web]: https://actix.rs
+//! [axum]: https://github.com/tokio-rs/axum
+//! [tower]: https://github.com/tower-rs/tower
+//! [`SealedSession`]: ../seal/struct.SealedSession.html
+//! [`SessionSealer`]: ../seal/struct.SessionSealer.html
+//! [`Session`]: struct.Session.html
+//! [`TokenStore`]: ../store/trait.TokenStore.html
+//! [`complete_login`]: fn.complete_login.html
+
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "rocket")]
+pub mod rocket;
+#[cfg(feature = "tower")]
+pub mod tower;
+
+use bridge::hyper::DiscordOAuthHyperRequester;
+use chrono::{DateTime, Duration, Utc};
+use hex;
+use model::{AccessTokenResponse, CurrentUser};
+use ring::rand::{SecureRandom, SystemRandom};
+#[cfg(any(
+    feature = "actix",
+    feature = "axum",
+    feature = "rocket",
+    feature = "tower",
+))]
+use seal::{SealedSession, SessionSealer};
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::result::Result as StdResult;
+use store::{StoredToken, TokenStore};
+use super::{Error, OAuthClient, Result, Scope};
+
+/// The name of the cookie holding a logged in user's [`Session`].
+///
+/// [`Session`]: struct.Session.html
+pub const SESSION_COOKIE: &str = "discord_session";
+
+/// The name of the cookie holding the state of a login in progress, which
+/// protects against cross-site request forgery.
+pub const STATE_COOKIE: &str = "discord_oauth_state";
+
+/// The number of days after which a session cookie expires, and the user needs
+/// to log in again.
+pub const SESSION_LIFETIME_DAYS: i64 = 30;
+
+/// The query parameters of the redirect back from Discord's authorization
+/// page.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CallbackQuery {
+    /// The authorization code, if the user authorized your application.
+    #[serde(default)]
+    pub code: Option<String>,
+    /// The error, if the user denied the authorization.
+    #[serde(default)]
+    pub error: Option<String>,
+    /// A description of the error, if any.
+    #[serde(default)]
+    pub error_description: Option<String>,
+    /// The state passed in the authorization URL.
+    #[serde(default)]
+    pub state: Option<String>,
+}
+
+/// The reason the redirect back from Discord could not complete a login.
+#[derive(Debug)]
+pub enum LoginError {
+    /// The user denied the authorization.
+    ///
+    /// This contains the error given by Discord.
+    Denied(String),
+    /// The redirect did not contain an authorization code.
+    MissingCode,
+    /// The state of the redirect did not match the state of the login in
+    /// progress.
+    InvalidState,
+    /// A request to Discord's API failed.
+    Request(Error),
+}
+
+impl From<Error> for LoginError {
+    fn from(err: Error) -> Self {
+        LoginError::Request(err)
+    }
+}
+
+impl Display for LoginError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            LoginError::Denied(ref error) => {
+                write!(f, "Authorization denied: {}", error)
+            },
+            LoginError::Request(ref inner) => Display::fmt(inner, f),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for LoginError {
+    fn description(&self) -> &str {
+        match *self {
+            LoginError::Denied(_) => "Authorization denied",
+            LoginError::MissingCode => "Missing authorization code",
+            LoginError::InvalidState => "Invalid authorization state",
+            LoginError::Request(ref inner) => inner.description(),
+        }
+    }
+}
+
+/// A logged in user, along with their grant.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Session {
+    /// The user's access token.
+    pub access_token: String,
+    /// The time at which the access token expires.
+    pub expires_at: DateTime<Utc>,
+    /// The refresh token to use when the access token expires.
+    pub refresh_token: String,
+    /// The scope that is granted.
+    pub scope: String,
+    /// The logged in user.
+    pub user: CurrentUser,
+}
+
+impl Session {
+    /// Creates a session from a user and the grant they authorized.
+    pub fn new(response: AccessTokenResponse, user: CurrentUser) -> Self {
+        Self {
+            access_token: response.access_token,
+            expires_at: expires_at(response.expires_in),
+            refresh_token: response.refresh_token,
+            scope: response.scope,
+            user,
+        }
+    }
+
+    /// Whether the access token has expired, and needs to be refreshed before
+    /// it can be used.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    /// Exchanges the refresh token for a fresh access token, updating the
+    /// session.
+    pub fn refresh<R>(&mut self, oauth: &OAuthClient, requester: &R)
+        -> Result<()> where R: DiscordOAuthHyperRequester {
+        let request = oauth.refresh_token_request(self.refresh_token.clone());
+        let response = requester.exchange_refresh_token(&request)?;
+
+        self.access_token = response.access_token;
+        self.expires_at = expires_at(response.expires_in);
+        self.refresh_token = response.refresh_token;
+        self.scope = response.scope;
+
+        Ok(())
+    }
+
+    /// Parses the scopes that are granted.
+    pub fn scopes(&self) -> Vec<Scope> {
+        Scope::parse_list(&self.scope)
+    }
+}
+
+impl<'a> From<&'a Session> for StoredToken {
+    fn from(session: &'a Session) -> Self {
+        Self {
+            access_token: session.access_token.clone(),
+            expires_at: session.expires_at.into(),
+            refresh_token: session.refresh_token.clone(),
+            revoked: false,
+            scope: session.scope.clone(),
+            token_type: "Bearer".to_owned(),
+        }
+    }
+}
+
+/// Completes a login from the query parameters of the redirect back from
+/// Discord, exchanging the code and retrieving the user.
+///
+/// The state of the redirect must match the expected state, as kept by the
+/// [`STATE_COOKIE`] when the login was started. The [`Scope::Identify`] scope
+/// must be one of the scopes requested by the [`OAuthClient`].
+///
+/// [`OAuthClient`]: ../struct.OAuthClient.html
+/// [`STATE_COOKIE`]: constant.STATE_COOKIE.html
+/// [`Scope::Identify`]: ../enum.Scope.html#variant.Identify
+pub fn complete_login<R>(
+    oauth: &OAuthClient,
+    requester: &R,
+    query: &CallbackQuery,
+    expected_state: Option<&str>,
+) -> StdResult<Session, LoginError> where R: DiscordOAuthHyperRequester {
+    if let Some(ref error) = query.error {
+        return Err(LoginError::Denied(error.clone()));
+    }
+
+    match (query.state.as_ref(), expected_state) {
+        (Some(state), Some(expected)) if state == expected => {},
+        _ => return Err(LoginError::InvalidState),
+    }
+
+    let code = query.code.as_ref().ok_or(LoginError::MissingCode)?;
+    let response = requester.exchange_code(
+        &oauth.code_exchange_request(code.clone()),
+    )?;
+    let user = requester.current_user(&response.access_token)?;
+
+    Ok(Session::new(response, user))
+}
+
+/// Retrieves the stored access token of a user, keyed by their ID, refreshing
+/// it if it has expired.
+///
+/// `None` is returned if no grant is stored or it was revoked, in which case
+/// the user needs to log in again.
+pub fn fresh_access_token<R, S>(
+    oauth: &OAuthClient,
+    requester: &R,
+    store: &S,
+    user_id: u64,
+) -> Result<Option<String>> where R: DiscordOAuthHyperRequester, S: TokenStore {
+    let key = user_id.to_string();
+
+    let token = match store.get(&key) {
+        Some(token) => token,
+        None => return Ok(None),
+    };
+
+    if token.revoked {
+        return Ok(None);
+    }
+
+    if !token.is_expired() {
+        return Ok(Some(token.access_token));
+    }
+
+    let request = oauth.refresh_token_request(token.refresh_token);
+
+    match requester.refresh_stored_token(store, &key, &request, |_, _| {}) {
+        Ok(response) => Ok(Some(response.access_token)),
+        Err(ref why) if why.is_revoked() => Ok(None),
+        Err(why) => Err(why),
+    }
+}
+
+/// Generates a random state to pass in an authorization URL.
+pub fn generate_state() -> String {
+    let mut bytes = [0; 16];
+
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("Error generating random state");
+
+    hex::encode(bytes)
+}
+
+fn expires_at(expires_in: u64) -> DateTime<Utc> {
+    Utc::now() + Duration::seconds(expires_in as i64)
+}
+
+/// Seals the session cookie value for the user of a completed login.
+///
+/// Only the user and their granted scopes are kept in the cookie, while their
+/// grant is kept in a token store keyed by their ID.
+#[cfg(any(
+    feature = "actix",
+    feature = "axum",
+    feature = "rocket",
+    feature = "tower",
+))]
+fn seal_session(sealer: &SessionSealer, session: Session) -> Result<String> {
+    let user_id = session.user.id;
+    let sealed = SealedSession::new(
+        user_id,
+        session.scopes(),
+        user_id.to_string(),
+        Duration::days(SESSION_LIFETIME_DAYS),
+    );
+
+    sealer.seal(&sealed.with_user(session.user))
+}
+
+/// Opens a session cookie value, yielding the logged in user and the scopes
+/// they granted if it is valid and has not expired.
+#[cfg(any(
+    feature = "actix",
+    feature = "axum",
+    feature = "rocket",
+    feature = "tower",
+))]
+fn open_session(sealer: &SessionSealer, value: &str)
+    -> Option<(CurrentUser, Vec<Scope>)> {
+    let session = sealer.open(value).ok()?;
+    let scopes = session.scopes;
+
+    session.user.map(|user| (user, scopes))
+}
+
+/// Finds the value of a cookie in the values of a request's `Cookie` headers.
+#[cfg(any(feature = "actix", feature = "axum", feature = "tower"))]
+fn cookie_value<'a, I>(headers: I, name: &str) -> Option<&'a str>
+    where I: IntoIterator<Item = &'a str> {
+    headers.into_iter()
+        .flat_map(|header| header.split(';'))
+        .filter_map(|pair| {
+            let mut parts = pair.trim().splitn(2, '=');
+
+            match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => Some((key, value)),
+                _ => None,
+            }
+        })
+        .find(|&(key, _)| key == name)
+        .map(|(_, value)| value)
+}
+
+/// Creates the value of a `Set-Cookie` header for a cookie that is sent along
+/// with the redirect back from Discord.
+///
+/// The cookie lasts for the given number of seconds, or until the browser is
+/// closed if none are given. Secure cookies are only sent over HTTPS.
+#[cfg(any(
+    feature = "actix",
+    feature = "axum",
+    feature = "rocket",
+    feature = "tower",
+))]
+fn lax_cookie(name: &str, value: &str, max_age: Option<i64>, secure: bool)
+    -> String {
+    let mut cookie = format!(
+        "{}={}; Path=/; HttpOnly; SameSite=Lax",
+        name,
+        value,
+    );
+
+    if let Some(max_age) = max_age {
+        cookie.push_str(&format!("; Max-Age={}", max_age));
+    }
+
+    if secure {
+        cookie.push_str("; Secure");
+    }
+
+    cookie
+}
+
+/// Creates the value of a `Set-Cookie` header removing a cookie.
+#[cfg(any(feature = "actix", feature = "axum", feature = "tower"))]
+fn removal_cookie(name: &str) -> String {
+    format!(
+        "{}=; Path=/; Max-Age=0; Expires=Thu, 01 Jan 1970 00:00:00 GMT",
+        name,
+    )
+}
+
+/// The value of the `Set-Cookie` header for the sealed state of a login in
+/// progress.
+#[cfg(any(
+    feature = "actix",
+    feature = "axum",
+    feature = "rocket",
+    feature = "tower",
+))]
+fn state_cookie(value: &str, secure: bool) -> String {
+    lax_cookie(STATE_COOKIE, value, None, secure)
+}
+
+/// The value of the `Set-Cookie` header for a sealed session cookie.
+#[cfg(any(
+    feature = "actix",
+    feature = "axum",
+    feature = "rocket",
+    feature = "tower",
+))]
+fn session_cookie(value: &str, secure: bool) -> String {
+    let max_age = Duration::days(SESSION_LIFETIME_DAYS).num_seconds();
+
+    lax_cookie(SESSION_COOKIE, value, Some(max_age), secure)
+}
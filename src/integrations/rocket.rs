@@ -0,0 +1,426 @@
+//! Integration with the [Rocket] web framework.
+//!
+//! The [`DiscordAuth`] fairing mounts routes for logging users in and out, and
+//! the [`DiscordUser`] and [`DiscordAccessToken`] request guards give handlers
+//! access to the logged in user.
+//!
+//! The logged in user is kept in a cookie, sealed by the [`SessionSealer`]
+//! given to the [`DiscordAuth`]. Their grant is kept in a [`TokenStore`] keyed
+//! by their ID, so that the access token never reaches the browser.
+//!
+//! Requests to Discord are made on tokio's blocking thread pool.
+//!
+//! # Examples
+//!
+//! Greet the logged in user, redirecting to the login route otherwise:
+//!
+//! ```rust,ignore
+//! #[macro_use] extern crate rocket;
+//! extern crate hyper;
+//! extern crate hyper_native_tls;
+//! extern crate serenity_oauth;
+//!
+//! use hyper::net::HttpsConnector;
+//! use hyper::Client;
+//! use hyper_native_tls::NativeTlsClient;
+//! use rocket::response::Redirect;
+//! use serenity_oauth::integrations::rocket::{DiscordAuth, DiscordUser};
+//! use serenity_oauth::seal::{SealingKey, SessionSealer};
+//! use serenity_oauth::OAuthClient;
+//!
+//! #[get("/")]
+//! fn index(user: DiscordUser) -> String {
+//!     format!("Hello, {}!", user.0.username)
+//! }
+//!
+//! #[get("/", rank = 2)]
+//! fn login() -> Redirect {
+//!     Redirect::to("/auth/login")
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let oauth = OAuthClient::new(
+//!         249608697955745802,
+//!         "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+//!         "http://localhost:8000/auth/callback",
+//!     );
+//!     let connector = HttpsConnector::new(NativeTlsClient::new().unwrap());
+//!     let client = Client::with_connector(connector);
+//!     let sealer = SessionSealer::new(SealingKey::generate());
+//!
+//!     let auth = DiscordAuth::new(oauth, client, sealer)
+//!         .with_secure_cookies(false);
+//!
+//!     rocket::build()
+//!         .attach(auth)
+//!         .mount("/", routes![index, login])
+//! }
+//! ```
+//!
+//! [Rocket]: https://rocket.rs
+//! [`DiscordAccessToken`]: struct.DiscordAccessToken.html
+//! [`DiscordAuth`]: struct.DiscordAuth.html
+//! [`DiscordUser`]: struct.DiscordUser.html
+//! [`SessionSealer`]: ../../seal/struct.SessionSealer.html
+//! [`TokenStore`]: ../../store/trait.TokenStore.html
+
+use bridge::hyper::HyperRequester;
+use futures_util::FutureExt;
+use hyper::client::Client as HyperClient;
+use model::CurrentUser;
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::http::{Cookie, CookieJar, Method, Status};
+use rocket::outcome::Outcome;
+use rocket::request::{self, FromRequest, Request};
+use rocket::response::Redirect;
+use rocket::route::{BoxFuture, Outcome as HandlerOutcome};
+use rocket::tokio::task;
+use rocket::{Build, Data, Rocket, Route};
+use seal::SessionSealer;
+use serde_urlencoded;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::result::Result as StdResult;
+use std::sync::Arc;
+use store::{MemoryTokenStore, TokenStore};
+use super::{
+    complete_login,
+    fresh_access_token,
+    generate_state,
+    open_session,
+    seal_session,
+    session_cookie,
+    state_cookie,
+    CallbackQuery,
+    LoginError,
+    SESSION_COOKIE,
+    STATE_COOKIE,
+};
+use ::OAuthClient;
+
+/// A fairing mounting routes that log users in with Discord and out again.
+///
+/// The following routes are mounted under the [`base`] path, which defaults
+/// to `/auth`:
+///
+/// - `GET /login`: redirects to Discord's authorization page;
+/// - `GET /callback`: completes the login, and redirects to [`after_login`];
+/// - `POST /logout`: ends the session, and redirects to [`after_logout`].
+///
+/// Logging out requires a `POST` request, so that other sites can not log
+/// users out by linking to the route.
+///
+/// The default redirect URI of the [`OAuthClient`] must point to the callback
+/// route, and its scopes must include [`Scope::Identify`].
+///
+/// [`OAuthClient`]: ../../struct.OAuthClient.html
+/// [`Scope::Identify`]: ../../enum.Scope.html#variant.Identify
+/// [`after_login`]: #structfield.after_login
+/// [`after_logout`]: #structfield.after_logout
+/// [`base`]: #structfield.base
+pub struct DiscordAuth {
+    /// The path users are redirected to after logging in.
+    ///
+    /// This defaults to `/`.
+    pub after_login: String,
+    /// The path users are redirected to after logging out.
+    ///
+    /// This defaults to `/`.
+    pub after_logout: String,
+    /// The path the routes are mounted under.
+    pub base: String,
+    oauth: OAuthClient,
+    requester: Arc<HyperRequester>,
+    sealer: SessionSealer,
+    secure_cookies: bool,
+    store: Arc<dyn TokenStore + Send + Sync>,
+}
+
+impl DiscordAuth {
+    /// Creates the fairing from your application's configuration, a hyper
+    /// Client able to make HTTPS requests, and the sealer to seal cookies with.
+    ///
+    /// Users' grants are kept in a [`MemoryTokenStore`] unless another store is
+    /// given with [`with_store`].
+    ///
+    /// [`MemoryTokenStore`]: ../../store/struct.MemoryTokenStore.html
+    /// [`with_store`]: #method.with_store
+    pub fn new(
+        oauth: OAuthClient,
+        client: HyperClient,
+        sealer: SessionSealer,
+    ) -> Self {
+        Self {
+            after_login: "/".to_owned(),
+            after_logout: "/".to_owned(),
+            base: "/auth".to_owned(),
+            requester: Arc::new(oauth.requester(client)),
+            oauth,
+            sealer,
+            secure_cookies: true,
+            store: Arc::new(MemoryTokenStore::new()),
+        }
+    }
+
+    /// Sets whether cookies are only sent over HTTPS.
+    ///
+    /// This defaults to `true`, and should only be turned off for local
+    /// development over plain HTTP.
+    pub fn with_secure_cookies(mut self, secure: bool) -> Self {
+        self.secure_cookies = secure;
+
+        self
+    }
+
+    /// Keeps users' grants in the given store.
+    pub fn with_store<S>(mut self, store: S) -> Self
+        where S: TokenStore + Send + Sync + 'static {
+        self.store = Arc::new(store);
+
+        self
+    }
+}
+
+// Rocket's traits are declared with `async_trait`, so their methods are
+// implemented with the signatures it expands to.
+impl Fairing for DiscordAuth {
+    fn info(&self) -> Info {
+        Info {
+            name: "Discord OAuth2",
+            kind: Kind::Ignite,
+        }
+    }
+
+    fn on_ignite<'life0, 'async_trait>(
+        &'life0 self,
+        rocket: Rocket<Build>,
+    ) -> Pin<Box<dyn Future<Output = fairing::Result> + Send + 'async_trait>>
+        where 'life0: 'async_trait, Self: 'async_trait {
+        let state = Arc::new(AuthState {
+            after_login: self.after_login.clone(),
+            after_logout: self.after_logout.clone(),
+            oauth: self.oauth.clone(),
+            requester: Arc::clone(&self.requester),
+            sealer: self.sealer.clone(),
+            secure_cookies: self.secure_cookies,
+            store: Arc::clone(&self.store),
+        });
+
+        let routes = vec![
+            Route::new(Method::Get, "/login", login),
+            Route::new(Method::Get, "/callback", callback),
+            Route::new(Method::Post, "/logout", logout),
+        ];
+
+        Box::pin(future::ready(
+            Ok(rocket.manage(state).mount(self.base.as_str(), routes)),
+        ))
+    }
+}
+
+/// A request guard yielding the logged in user.
+///
+/// The request is forwarded with a `401 Unauthorized` status if no user is
+/// logged in, such as to a route redirecting to the login route.
+#[derive(Clone, Debug)]
+pub struct DiscordUser(pub CurrentUser);
+
+impl<'r> FromRequest<'r> for DiscordUser {
+    type Error = ();
+
+    fn from_request<'life0, 'async_trait>(request: &'r Request<'life0>)
+        -> GuardFuture<'async_trait, Self>
+        where 'r: 'async_trait, 'life0: 'async_trait {
+        let outcome = match auth_state(request) {
+            Some(state) => match read_user(&state.sealer, request.cookies()) {
+                Some(user) => Outcome::Success(DiscordUser(user)),
+                None => Outcome::Forward(Status::Unauthorized),
+            },
+            None => Outcome::Error((Status::InternalServerError, ())),
+        };
+
+        Box::pin(future::ready(outcome))
+    }
+}
+
+/// A request guard yielding an access token of the logged in user that has
+/// not expired.
+///
+/// If the stored access token has expired, it is refreshed first. The request
+/// is forwarded with a `401 Unauthorized` status if no user is logged in or
+/// their grant was revoked, in which case the session is ended, and fails with
+/// a `502 Bad Gateway` status if the refresh fails otherwise.
+#[derive(Clone, Debug)]
+pub struct DiscordAccessToken(pub String);
+
+impl<'r> FromRequest<'r> for DiscordAccessToken {
+    type Error = ();
+
+    fn from_request<'life0, 'async_trait>(request: &'r Request<'life0>)
+        -> GuardFuture<'async_trait, Self>
+        where 'r: 'async_trait, 'life0: 'async_trait {
+        let state = match auth_state(request) {
+            Some(state) => Arc::clone(state),
+            None => return Box::pin(future::ready(
+                Outcome::Error((Status::InternalServerError, ())),
+            )),
+        };
+        let cookies = request.cookies();
+
+        let user = match read_user(&state.sealer, cookies) {
+            Some(user) => user,
+            None => return Box::pin(future::ready(
+                Outcome::Forward(Status::Unauthorized),
+            )),
+        };
+
+        let future = task::spawn_blocking(move || {
+            fresh_access_token(
+                &state.oauth,
+                &*state.requester,
+                &state.store,
+                user.id,
+            )
+        });
+
+        Box::pin(future.map(move |result| match result {
+            Ok(Ok(Some(token))) => Outcome::Success(DiscordAccessToken(token)),
+            Ok(Ok(None)) => {
+                cookies.remove(removal_cookie(SESSION_COOKIE));
+
+                Outcome::Forward(Status::Unauthorized)
+            },
+            Ok(Err(_)) => Outcome::Error((Status::BadGateway, ())),
+            Err(_) => Outcome::Error((Status::InternalServerError, ())),
+        }))
+    }
+}
+
+/// The future of a request guard, as expanded by `async_trait`.
+type GuardFuture<'a, T> = Pin<Box<
+    dyn Future<Output = request::Outcome<T, ()>> + Send + 'a
+>>;
+
+/// The state managed by the [`DiscordAuth`] fairing.
+///
+/// [`DiscordAuth`]: struct.DiscordAuth.html
+struct AuthState {
+    after_login: String,
+    after_logout: String,
+    oauth: OAuthClient,
+    requester: Arc<HyperRequester>,
+    sealer: SessionSealer,
+    secure_cookies: bool,
+    store: Arc<dyn TokenStore + Send + Sync>,
+}
+
+fn login<'r>(request: &'r Request, _: Data<'r>) -> BoxFuture<'r> {
+    let state = match auth_state(request) {
+        Some(state) => state,
+        None => return error(Status::InternalServerError),
+    };
+    let csrf = generate_state();
+
+    let value = match state.sealer.seal_value(STATE_COOKIE, &csrf) {
+        Ok(value) => value,
+        Err(_) => return error(Status::InternalServerError),
+    };
+
+    add_cookie(request.cookies(), state_cookie(&value, state.secure_cookies));
+    let url = state.oauth.authorization_url(Some(&csrf));
+
+    Box::pin(future::ready(HandlerOutcome::from(request, Redirect::to(url))))
+}
+
+fn callback<'r>(request: &'r Request, _: Data<'r>) -> BoxFuture<'r> {
+    let state = match auth_state(request) {
+        Some(state) => Arc::clone(state),
+        None => return error(Status::InternalServerError),
+    };
+    let query = request.uri()
+        .query()
+        .and_then(|query| serde_urlencoded::from_str(query.as_str()).ok())
+        .unwrap_or_else(CallbackQuery::default);
+
+    let cookies = request.cookies();
+    let expected = cookies.get(STATE_COOKIE).and_then(|cookie| {
+        state.sealer.open_value::<String>(STATE_COOKIE, cookie.value()).ok()
+    });
+    cookies.remove(removal_cookie(STATE_COOKIE));
+    let after_login = state.after_login.clone();
+    let secure = state.secure_cookies;
+
+    let future = task::spawn_blocking(move || {
+        let session = complete_login(
+            &state.oauth,
+            &*state.requester,
+            &query,
+            expected.as_ref().map(|state| &**state),
+        )?;
+
+        state.store.insert(&session.user.id.to_string(), (&session).into());
+
+        Ok(seal_session(&state.sealer, session))
+    });
+
+    Box::pin(future.map(move |result: StdResult<StdResult<_, LoginError>, _>| {
+        match result {
+            Ok(Ok(Ok(value))) => {
+                add_cookie(cookies, session_cookie(&value, secure));
+
+                HandlerOutcome::from(request, Redirect::to(after_login))
+            },
+            Ok(Ok(Err(_))) => {
+                HandlerOutcome::error(Status::InternalServerError)
+            },
+            Ok(Err(LoginError::Denied(_))) => {
+                HandlerOutcome::error(Status::Forbidden)
+            },
+            Ok(Err(LoginError::Request(_))) => {
+                HandlerOutcome::error(Status::BadGateway)
+            },
+            Ok(Err(_)) => HandlerOutcome::error(Status::BadRequest),
+            Err(_) => HandlerOutcome::error(Status::InternalServerError),
+        }
+    }))
+}
+
+fn logout<'r>(request: &'r Request, _: Data<'r>) -> BoxFuture<'r> {
+    let state = match auth_state(request) {
+        Some(state) => state,
+        None => return error(Status::InternalServerError),
+    };
+
+    request.cookies().remove(removal_cookie(SESSION_COOKIE));
+    let redirect = Redirect::to(state.after_logout.clone());
+
+    Box::pin(future::ready(HandlerOutcome::from(request, redirect)))
+}
+
+/// Adds a cookie from the value of a `Set-Cookie` header.
+fn add_cookie(cookies: &CookieJar, header: String) {
+    if let Ok(cookie) = Cookie::parse(header) {
+        cookies.add(cookie);
+    }
+}
+
+fn auth_state<'r>(request: &'r Request) -> Option<&'r Arc<AuthState>> {
+    request.rocket().state::<Arc<AuthState>>()
+}
+
+fn error<'r>(status: Status) -> BoxFuture<'r> {
+    Box::pin(future::ready(HandlerOutcome::error(status)))
+}
+
+fn read_user(sealer: &SessionSealer, cookies: &CookieJar)
+    -> Option<CurrentUser> {
+    let cookie = cookies.get(SESSION_COOKIE)?;
+
+    open_session(sealer, cookie.value()).map(|(user, _)| user)
+}
+
+/// A cookie removing the cookie of the given name, set for the same path.
+fn removal_cookie(name: &'static str) -> Cookie<'static> {
+    Cookie::build(name).path("/").build()
+}
@@ -0,0 +1,445 @@
+//! A [tower] middleware requiring users to log in with Discord.
+//!
+//! The [`DiscordAuthLayer`] works with any server built on tower services,
+//! such as axum, hyper, tonic-web and warp. Each request it wraps is handled
+//! as follows:
+//!
+//! - requests to the path of the redirect URI complete the login, and are
+//!   redirected back to the path the user originally requested;
+//! - requests of users that are not logged in are redirected to Discord's
+//!   authorization page;
+//! - requests of logged in users are passed on, with the [`DiscordIdentity`]
+//!   of the user and a [`DiscordAccessToken`] that has not expired inserted
+//!   into the request's extensions.
+//!
+//! The logged in user is kept in a cookie, sealed by the [`SessionSealer`]
+//! given to the layer. Their grant is kept in a [`TokenStore`] keyed by their
+//! ID, so that the access token never reaches the browser.
+//!
+//! Requests to Discord are made on tokio's blocking thread pool.
+//!
+//! # Examples
+//!
+//! Require users to log in to an axum application:
+//!
+//! ```rust,ignore
+//! extern crate axum;
+//! extern crate hyper;
+//! extern crate hyper_native_tls;
+//! extern crate serenity_oauth;
+//!
+//! use axum::routing::get;
+//! use axum::{Extension, Router};
+//! use hyper::net::HttpsConnector;
+//! use hyper::Client;
+//! use hyper_native_tls::NativeTlsClient;
+//! use serenity_oauth::integrations::tower::{
+//!     DiscordAuthLayer,
+//!     DiscordIdentity,
+//! };
+//! use serenity_oauth::seal::{SealingKey, SessionSealer};
+//! use serenity_oauth::OAuthClient;
+//!
+//! async fn index(Extension(identity): Extension<DiscordIdentity>) -> String {
+//!     format!("Hello, {}!", identity.user.username)
+//! }
+//!
+//! fn app() -> Router {
+//!     let oauth = OAuthClient::new(
+//!         249608697955745802,
+//!         "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+//!         "http://localhost:3000/callback",
+//!     );
+//!     let connector = HttpsConnector::new(NativeTlsClient::new().unwrap());
+//!     let client = Client::with_connector(connector);
+//!     let sealer = SessionSealer::new(SealingKey::generate());
+//!
+//!     let auth = DiscordAuthLayer::new(oauth, client, sealer)
+//!         .unwrap()
+//!         .with_secure_cookies(false);
+//!
+//!     Router::new()
+//!         .route("/", get(index))
+//!         .layer(auth)
+//! }
+//! ```
+//!
+//! [tower]: https://github.com/tower-rs/tower
+//! [`DiscordAccessToken`]: struct.DiscordAccessToken.html
+//! [`DiscordAuthLayer`]: struct.DiscordAuthLayer.html
+//! [`DiscordIdentity`]: struct.DiscordIdentity.html
+//! [`SessionSealer`]: ../../seal/struct.SessionSealer.html
+//! [`TokenStore`]: ../../store/trait.TokenStore.html
+
+use bridge::hyper::HyperRequester;
+use config::ConfigError;
+use futures_util::future::Either;
+use futures_util::FutureExt;
+use http::header::{HeaderMap, HeaderValue, COOKIE, LOCATION, SET_COOKIE};
+use http::{Request, Response, StatusCode, Uri};
+use hyper::client::Client as HyperClient;
+use model::CurrentUser;
+use seal::SessionSealer;
+use serde_urlencoded;
+use std::future::{self, Future};
+use std::mem;
+use std::pin::Pin;
+use std::result::Result as StdResult;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use store::{MemoryTokenStore, TokenStore};
+use super::{
+    complete_login,
+    cookie_value,
+    fresh_access_token,
+    generate_state,
+    open_session,
+    removal_cookie,
+    seal_session,
+    session_cookie,
+    state_cookie,
+    CallbackQuery,
+    LoginError,
+    SESSION_COOKIE,
+    STATE_COOKIE,
+};
+use tokio::task;
+use tower::{Layer, Service};
+use ::{Error, OAuthClient, Result, Scope};
+
+/// The logged in user, inserted into the extensions of requests passed on by
+/// the [`DiscordAuthService`].
+///
+/// [`DiscordAuthService`]: struct.DiscordAuthService.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DiscordIdentity {
+    /// The scopes the user granted.
+    pub scopes: Vec<Scope>,
+    /// The logged in user.
+    pub user: CurrentUser,
+}
+
+/// An access token of the logged in user that has not expired, inserted into
+/// the extensions of requests passed on by the [`DiscordAuthService`].
+///
+/// [`DiscordAuthService`]: struct.DiscordAuthService.html
+#[derive(Clone, Debug)]
+pub struct DiscordAccessToken(pub String);
+
+/// A layer requiring users to log in with Discord.
+///
+/// The login is completed on the path of the default redirect URI of the
+/// [`OAuthClient`], and the scopes it requests must include
+/// [`Scope::Identify`].
+///
+/// [`OAuthClient`]: ../../struct.OAuthClient.html
+/// [`Scope::Identify`]: ../../enum.Scope.html#variant.Identify
+#[derive(Clone)]
+pub struct DiscordAuthLayer {
+    callback_path: String,
+    oauth: OAuthClient,
+    requester: Arc<HyperRequester>,
+    sealer: SessionSealer,
+    secure_cookies: bool,
+    store: Arc<dyn TokenStore + Send + Sync>,
+}
+
+impl DiscordAuthLayer {
+    /// Creates the layer from your application's configuration, a hyper Client
+    /// able to make HTTPS requests, and the sealer to seal cookies with.
+    ///
+    /// Users' grants are kept in a [`MemoryTokenStore`] unless another store is
+    /// given with [`with_store`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Malformed`] if the default redirect URI of the
+    /// [`OAuthClient`] can not be parsed, as the path to complete the login on
+    /// would be unknown.
+    ///
+    /// [`ConfigError::Malformed`]: ../../config/enum.ConfigError.html#variant.Malformed
+    /// [`MemoryTokenStore`]: ../../store/struct.MemoryTokenStore.html
+    /// [`OAuthClient`]: ../../struct.OAuthClient.html
+    /// [`with_store`]: #method.with_store
+    pub fn new(
+        oauth: OAuthClient,
+        client: HyperClient,
+        sealer: SessionSealer,
+    ) -> Result<Self> {
+        let uri = oauth.redirect_uri().parse::<Uri>().map_err(|why| {
+            Error::Config(ConfigError::Malformed {
+                key: "redirect_uri".to_owned(),
+                reason: why.to_string(),
+            })
+        })?;
+
+        Ok(Self {
+            callback_path: uri.path().to_owned(),
+            requester: Arc::new(oauth.requester(client)),
+            oauth,
+            sealer,
+            secure_cookies: true,
+            store: Arc::new(MemoryTokenStore::new()),
+        })
+    }
+
+    /// Sets whether cookies are only sent over HTTPS.
+    ///
+    /// This defaults to `true`, and should only be turned off for local
+    /// development over plain HTTP.
+    pub fn with_secure_cookies(mut self, secure: bool) -> Self {
+        self.secure_cookies = secure;
+
+        self
+    }
+
+    /// Keeps users' grants in the given store.
+    pub fn with_store<S>(mut self, store: S) -> Self
+        where S: TokenStore + Send + Sync + 'static {
+        self.store = Arc::new(store);
+
+        self
+    }
+
+    fn identity(&self, headers: &HeaderMap) -> Option<DiscordIdentity> {
+        let value = read_cookie(headers, SESSION_COOKIE)?;
+        let (user, scopes) = open_session(&self.sealer, value)?;
+
+        Some(DiscordIdentity {
+            scopes,
+            user,
+        })
+    }
+
+    /// Creates a response redirecting to Discord's authorization page, which
+    /// returns to the given path once the login is complete.
+    fn login<B>(&self, return_to: String) -> Response<B> where B: Default {
+        let pending = PendingLogin {
+            return_to,
+            state: generate_state(),
+        };
+        let url = self.oauth.authorization_url(Some(&pending.state));
+
+        let mut response = redirect(&url);
+
+        if let Ok(value) = self.sealer.seal_value(STATE_COOKIE, &pending) {
+            let cookie = state_cookie(&value, self.secure_cookies);
+            set_cookie(&mut response, &cookie);
+        }
+
+        response
+    }
+}
+
+impl<S> Layer<S> for DiscordAuthLayer {
+    type Service = DiscordAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DiscordAuthService {
+            auth: Arc::new(self.clone()),
+            inner,
+        }
+    }
+}
+
+/// The service created by a [`DiscordAuthLayer`].
+///
+/// [`DiscordAuthLayer`]: struct.DiscordAuthLayer.html
+#[derive(Clone)]
+pub struct DiscordAuthService<S> {
+    auth: Arc<DiscordAuthLayer>,
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for DiscordAuthService<S>
+    where S: Service<Request<ReqBody>, Response = Response<ResBody>>
+              + Clone
+              + Send
+              + 'static,
+          S::Error: Send + 'static,
+          S::Future: Send + 'static,
+          ReqBody: Send + 'static,
+          ResBody: Default + Send + 'static {
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<
+        dyn Future<Output = StdResult<Response<ResBody>, S::Error>> + Send
+    >>;
+
+    fn poll_ready(&mut self, cx: &mut Context)
+        -> Poll<StdResult<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        // The clone may not be ready, so call the instance that is.
+        let clone = self.inner.clone();
+        let mut inner = mem::replace(&mut self.inner, clone);
+        let auth = Arc::clone(&self.auth);
+
+        if request.uri().path() == auth.callback_path {
+            let query = request.uri().query();
+
+            return Box::pin(callback(auth, query, request.headers()).map(Ok));
+        }
+
+        let identity = match auth.identity(request.headers()) {
+            Some(identity) => identity,
+            None => {
+                let response = auth.login(return_path(request.uri()));
+
+                return Box::pin(future::ready(Ok(response)));
+            },
+        };
+
+        let user_id = identity.user.id;
+        let blocking = Arc::clone(&auth);
+
+        let future = task::spawn_blocking(move || {
+            fresh_access_token(
+                &blocking.oauth,
+                &*blocking.requester,
+                &blocking.store,
+                user_id,
+            )
+        });
+
+        Box::pin(future.then(move |result| match result {
+            Ok(Ok(Some(token))) => {
+                request.extensions_mut().insert(identity);
+                request.extensions_mut().insert(DiscordAccessToken(token));
+
+                Either::Left(inner.call(request))
+            },
+            Ok(Ok(None)) => {
+                let response = auth.login(return_path(request.uri()));
+
+                Either::Right(future::ready(Ok(response)))
+            },
+            Ok(Err(_)) => {
+                let response = status(StatusCode::BAD_GATEWAY);
+
+                Either::Right(future::ready(Ok(response)))
+            },
+            Err(_) => {
+                let response = status(StatusCode::INTERNAL_SERVER_ERROR);
+
+                Either::Right(future::ready(Ok(response)))
+            },
+        }))
+    }
+}
+
+/// A login in progress, as kept in the state cookie.
+#[derive(Deserialize, Serialize)]
+struct PendingLogin {
+    return_to: String,
+    state: String,
+}
+
+/// Completes a login from the redirect back from Discord.
+fn callback<B>(
+    auth: Arc<DiscordAuthLayer>,
+    query: Option<&str>,
+    headers: &HeaderMap,
+) -> impl Future<Output = Response<B>> where B: Default {
+    let query = query
+        .and_then(|query| serde_urlencoded::from_str(query).ok())
+        .unwrap_or_else(CallbackQuery::default);
+    let pending = read_cookie(headers, STATE_COOKIE)
+        .and_then(|value| {
+            auth.sealer.open_value::<PendingLogin>(STATE_COOKIE, value).ok()
+        });
+    let (expected, return_to) = match pending {
+        Some(ref pending) if !is_local_path(&pending.return_to) => {
+            (Some(pending.state.clone()), "/".to_owned())
+        },
+        Some(pending) => (Some(pending.state), pending.return_to),
+        None => (None, "/".to_owned()),
+    };
+    let blocking = Arc::clone(&auth);
+
+    let future = task::spawn_blocking(move || {
+        let session = complete_login(
+            &blocking.oauth,
+            &*blocking.requester,
+            &query,
+            expected.as_ref().map(|state| &**state),
+        )?;
+
+        blocking.store.insert(&session.user.id.to_string(), (&session).into());
+
+        Ok(seal_session(&blocking.sealer, session))
+    });
+
+    future.map(move |result: StdResult<StdResult<_, LoginError>, _>| {
+        let mut response = match result {
+            Ok(Ok(Ok(value))) => {
+                let cookie = session_cookie(&value, auth.secure_cookies);
+                let mut response = redirect(&return_to);
+                set_cookie(&mut response, &cookie);
+
+                response
+            },
+            Ok(Ok(Err(_))) => status(StatusCode::INTERNAL_SERVER_ERROR),
+            Ok(Err(LoginError::Denied(_))) => status(StatusCode::FORBIDDEN),
+            Ok(Err(LoginError::Request(_))) => status(StatusCode::BAD_GATEWAY),
+            Ok(Err(_)) => status(StatusCode::BAD_REQUEST),
+            Err(_) => status(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+
+        set_cookie(&mut response, &removal_cookie(STATE_COOKIE));
+
+        response
+    })
+}
+
+/// Finds the value of a cookie in the headers of a request.
+fn read_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    let values = headers.get_all(COOKIE)
+        .iter()
+        .filter_map(|header| header.to_str().ok());
+
+    cookie_value(values, name)
+}
+
+fn redirect<B>(location: &str) -> Response<B> where B: Default {
+    let mut response = status(StatusCode::FOUND);
+
+    if let Ok(value) = HeaderValue::from_str(location) {
+        response.headers_mut().insert(LOCATION, value);
+    }
+
+    response
+}
+
+/// Whether a path can only refer to this site, as opposed to paths such as
+/// `//example.com` that browsers treat as another site.
+fn is_local_path(path: &str) -> bool {
+    path.starts_with('/')
+        && !path.starts_with("//")
+        && !path.starts_with("/\\")
+}
+
+/// The path and query of a request, to return to once the login is complete.
+///
+/// This falls back to `/` unless the path refers to this site.
+fn return_path(uri: &Uri) -> String {
+    match uri.path_and_query() {
+        Some(path) if is_local_path(path.as_str()) => path.as_str().to_owned(),
+        _ => "/".to_owned(),
+    }
+}
+
+fn set_cookie<B>(response: &mut Response<B>, cookie: &str) {
+    if let Ok(value) = HeaderValue::from_str(cookie) {
+        response.headers_mut().append(SET_COOKIE, value);
+    }
+}
+
+fn status<B>(status: StatusCode) -> Response<B> where B: Default {
+    let mut response = Response::new(B::default());
+    *response.status_mut() = status;
+
+    response
+}
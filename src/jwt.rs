@@ -0,0 +1,637 @@
+//! Issuing of your own JSON Web Tokens to users that logged in with Discord.
+//!
+//! After a user authorized your application, a [`JwtIssuer`] turns their grant
+//! into short-lived [`SessionClaims`] signed with your own key, such as for an
+//! API gateway. Other services validate them with a [`JwtVerifier`], without
+//! having to make requests to Discord.
+//!
+//! Tokens are signed with either HMAC-SHA256 (`HS256`) or Ed25519 (`EdDSA`).
+//!
+//! [`JwtIssuer`]: struct.JwtIssuer.html
+//! [`JwtVerifier`]: struct.JwtVerifier.html
+//! [`SessionClaims`]: struct.SessionClaims.html
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use model::{AccessTokenResponse, CurrentUser, CurrentUserGuild};
+use ring::hmac;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json;
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use super::{Error, Result, Scope};
+
+/// The number of seconds issued tokens are valid for by default.
+pub const DEFAULT_LIFETIME: i64 = 900;
+
+/// The number of seconds of clock skew tolerated when checking the expiry of
+/// tokens by default.
+pub const DEFAULT_LEEWAY: i64 = 60;
+
+/// The reason a token could not be signed or failed verification.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JwtError {
+    /// The token's audience is not the expected audience.
+    Audience,
+    /// An HMAC secret is empty, which would let anyone forge tokens.
+    EmptySecret,
+    /// The token has expired.
+    Expired,
+    /// The token's issuer is not the expected issuer.
+    Issuer(String),
+    /// The token is not a well-formed JSON Web Token.
+    Malformed,
+    /// The token's signature does not match its contents.
+    Signature,
+    /// The token's header names the ID of a key the verifier does not have.
+    UnknownKey(String),
+    /// The token was signed with an algorithm other than that of the
+    /// verifier's key.
+    UnsupportedAlgorithm(String),
+}
+
+impl Display for JwtError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            JwtError::Issuer(ref issuer) => {
+                write!(f, "Unexpected token issuer: {}", issuer)
+            },
+            JwtError::UnknownKey(ref kid) => {
+                write!(f, "Unknown token key ID: {}", kid)
+            },
+            JwtError::UnsupportedAlgorithm(ref alg) => {
+                write!(f, "Unsupported token algorithm: {}", alg)
+            },
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for JwtError {
+    fn description(&self) -> &str {
+        match *self {
+            JwtError::Audience => "Token audience mismatch",
+            JwtError::EmptySecret => "Empty HMAC secret",
+            JwtError::Expired => "Token expired",
+            JwtError::Issuer(_) => "Unexpected token issuer",
+            JwtError::Malformed => "Malformed token",
+            JwtError::Signature => "Invalid token signature",
+            JwtError::UnknownKey(_) => "Unknown token key ID",
+            JwtError::UnsupportedAlgorithm(_) => "Unsupported token algorithm",
+        }
+    }
+}
+
+/// The claims of a token issued to a user that logged in with Discord.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SessionClaims {
+    /// The audience the token is intended for, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    /// The time at which the token expires, as a Unix timestamp.
+    pub exp: i64,
+    /// The IDs of the guilds the user is a member of, if they were included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guilds: Option<Vec<String>>,
+    /// The time at which the token was issued, as a Unix timestamp.
+    pub iat: i64,
+    /// The issuer of the token.
+    pub iss: String,
+    /// The scopes the user granted, separated by spaces.
+    pub scope: String,
+    /// The ID of the user.
+    pub sub: String,
+}
+
+impl SessionClaims {
+    /// Parses the IDs of the guilds the user is a member of, if they were
+    /// included.
+    pub fn guild_ids(&self) -> Option<Vec<u64>> {
+        self.guilds.as_ref().map(|guilds| {
+            guilds.iter().filter_map(|id| id.parse().ok()).collect()
+        })
+    }
+
+    /// Whether the user granted the given scope.
+    pub fn has_scope(&self, scope: &Scope) -> bool {
+        self.scopes().contains(scope)
+    }
+
+    /// Parses the scopes the user granted.
+    pub fn scopes(&self) -> Vec<Scope> {
+        Scope::parse_list(&self.scope)
+    }
+
+    /// Parses the user's ID from the [`sub`] claim.
+    ///
+    /// [`sub`]: #structfield.sub
+    pub fn user_id(&self) -> Option<u64> {
+        self.sub.parse().ok()
+    }
+}
+
+/// A key to sign tokens with.
+#[derive(Clone)]
+pub enum JwtSigningKey {
+    /// A secret shared with the verifiers, for `HS256`.
+    Hmac(Vec<u8>),
+    /// An Ed25519 private key, for `EdDSA`.
+    Ed25519(SigningKey),
+}
+
+impl JwtSigningKey {
+    /// Creates a key from a secret shared with the verifiers, for `HS256`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JwtError::EmptySecret`] if the secret is empty.
+    ///
+    /// [`JwtError::EmptySecret`]: enum.JwtError.html#variant.EmptySecret
+    pub fn hmac(secret: Vec<u8>) -> Result<Self> {
+        check_secret(&secret)?;
+
+        Ok(JwtSigningKey::Hmac(secret))
+    }
+
+    /// The key to verify tokens signed with this key with.
+    pub fn verifying_key(&self) -> JwtVerifyingKey {
+        match *self {
+            JwtSigningKey::Hmac(ref secret) => {
+                JwtVerifyingKey::Hmac(secret.clone())
+            },
+            JwtSigningKey::Ed25519(ref key) => {
+                JwtVerifyingKey::Ed25519(key.verifying_key())
+            },
+        }
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        Ok(match *self {
+            JwtSigningKey::Hmac(ref secret) => {
+                check_secret(secret)?;
+                let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+
+                hmac::sign(&key, message).as_ref().to_vec()
+            },
+            JwtSigningKey::Ed25519(ref key) => {
+                key.sign(message).to_bytes().to_vec()
+            },
+        })
+    }
+}
+
+/// A key to verify the signatures of tokens with.
+#[derive(Clone)]
+pub enum JwtVerifyingKey {
+    /// A secret shared with the issuer, for `HS256`.
+    Hmac(Vec<u8>),
+    /// An Ed25519 public key, for `EdDSA`.
+    Ed25519(VerifyingKey),
+}
+
+impl JwtVerifyingKey {
+    /// Creates a key from a secret shared with the issuer, for `HS256`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JwtError::EmptySecret`] if the secret is empty.
+    ///
+    /// [`JwtError::EmptySecret`]: enum.JwtError.html#variant.EmptySecret
+    pub fn hmac(secret: Vec<u8>) -> Result<Self> {
+        check_secret(&secret)?;
+
+        Ok(JwtVerifyingKey::Hmac(secret))
+    }
+
+    fn alg(&self) -> &'static str {
+        match *self {
+            JwtVerifyingKey::Hmac(_) => "HS256",
+            JwtVerifyingKey::Ed25519(_) => "EdDSA",
+        }
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        let valid = match *self {
+            JwtVerifyingKey::Hmac(ref secret) => {
+                check_secret(secret)?;
+                let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+
+                hmac::verify(&key, message, signature).is_ok()
+            },
+            JwtVerifyingKey::Ed25519(ref key) => {
+                Signature::from_slice(signature)
+                    .map(|signature| key.verify(message, &signature).is_ok())
+                    .unwrap_or(false)
+            },
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(invalid(JwtError::Signature))
+        }
+    }
+}
+
+/// An issuer of tokens to users that logged in with Discord.
+///
+/// # Examples
+///
+/// Issue a token after a login, and verify it in another service:
+///
+/// ```rust
+/// extern crate serde_json;
+/// extern crate serenity_oauth;
+///
+/// use serenity_oauth::jwt::{JwtIssuer, JwtSigningKey, JwtVerifier};
+/// use serenity_oauth::model::{AccessTokenResponse, CurrentUser};
+/// use serenity_oauth::Scope;
+///
+/// # fn main() {
+/// # let response: AccessTokenResponse = serde_json::from_str(r#"{
+/// #     "access_token": "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+/// #     "expires_in": 604800,
+/// #     "refresh_token": "D43f5y0ahjqew82jZ4NViEr2YafMKhue",
+/// #     "scope": "identify guilds",
+/// #     "token_type": "Bearer"
+/// # }"#).unwrap();
+/// # let user: CurrentUser = serde_json::from_str(r#"{
+/// #     "id": "80351110224678912",
+/// #     "username": "nelly",
+/// #     "discriminator": "0"
+/// # }"#).unwrap();
+/// #
+/// let secret = b"a secret shared with the gateway".to_vec();
+/// let key = JwtSigningKey::hmac(secret).unwrap();
+/// let verifier = JwtVerifier::new("https://example.com", key.verifying_key());
+/// let issuer = JwtIssuer::new("https://example.com", key);
+///
+/// // After exchanging the code and retrieving the user.
+/// let token = issuer.issue(&response, &user, None).unwrap();
+///
+/// let claims = verifier.verify(&token).unwrap();
+/// assert_eq!(claims.user_id(), Some(80351110224678912));
+/// assert!(claims.has_scope(&Scope::Guilds));
+///
+/// // Tokens can not be verified once tampered with.
+/// let tampered = format!("{}x", token);
+/// assert!(verifier.verify(&tampered).is_err());
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct JwtIssuer {
+    /// The audience of issued tokens, if any.
+    pub audience: Option<String>,
+    /// The issuer of issued tokens, such as your service's URL.
+    pub issuer: String,
+    /// The key to sign tokens with.
+    pub key: JwtSigningKey,
+    /// The ID of the key, included in the header of issued tokens if set.
+    ///
+    /// Verifiers holding several keys pick the key to verify a token with by
+    /// this ID, as added with [`JwtVerifier::with_key`].
+    ///
+    /// [`JwtVerifier::with_key`]: struct.JwtVerifier.html#method.with_key
+    pub key_id: Option<String>,
+    /// The number of seconds issued tokens are valid for.
+    ///
+    /// This defaults to [`DEFAULT_LIFETIME`].
+    ///
+    /// [`DEFAULT_LIFETIME`]: constant.DEFAULT_LIFETIME.html
+    pub lifetime: i64,
+}
+
+impl JwtIssuer {
+    /// Creates an issuer of tokens signed with the given key.
+    pub fn new<S>(issuer: S, key: JwtSigningKey) -> Self
+        where S: Into<String> {
+        Self {
+            audience: None,
+            issuer: issuer.into(),
+            key,
+            key_id: None,
+            lifetime: DEFAULT_LIFETIME,
+        }
+    }
+
+    /// Creates the claims of a token for a user and the grant they authorized.
+    ///
+    /// The guilds the user is a member of, as retrieved with the
+    /// [`Scope::Guilds`] scope, are included if given.
+    ///
+    /// [`Scope::Guilds`]: ../enum.Scope.html#variant.Guilds
+    pub fn claims(
+        &self,
+        response: &AccessTokenResponse,
+        user: &CurrentUser,
+        guilds: Option<&[CurrentUserGuild]>,
+    ) -> SessionClaims {
+        self.claims_at(response, user, guilds, Utc::now())
+    }
+
+    /// Creates the claims of a token issued at the given time.
+    ///
+    /// Refer to [`claims`] for more information.
+    ///
+    /// [`claims`]: #method.claims
+    pub fn claims_at(
+        &self,
+        response: &AccessTokenResponse,
+        user: &CurrentUser,
+        guilds: Option<&[CurrentUserGuild]>,
+        now: DateTime<Utc>,
+    ) -> SessionClaims {
+        let guilds = guilds.map(|guilds| {
+            guilds.iter().map(|guild| guild.id.to_string()).collect()
+        });
+
+        SessionClaims {
+            aud: self.audience.clone(),
+            exp: now.timestamp().saturating_add(self.lifetime),
+            iat: now.timestamp(),
+            iss: self.issuer.clone(),
+            scope: response.scope.clone(),
+            sub: user.id.to_string(),
+            guilds,
+        }
+    }
+
+    /// Signs the given claims into a token.
+    pub fn sign(&self, claims: &SessionClaims) -> Result<String> {
+        let header = Header {
+            alg: self.key.verifying_key().alg().to_owned(),
+            kid: self.key_id.clone(),
+            typ: Some("JWT".to_owned()),
+        };
+
+        let mut token = encode_json(&header)?;
+        token.push('.');
+        token.push_str(&encode_json(claims)?);
+
+        let signature = self.key.sign(token.as_bytes())?;
+        token.push('.');
+        token.push_str(&URL_SAFE_NO_PAD.encode(signature));
+
+        Ok(token)
+    }
+
+    /// Creates and signs a token for a user and the grant they authorized.
+    ///
+    /// Refer to [`claims`] for more information.
+    ///
+    /// [`claims`]: #method.claims
+    pub fn issue(
+        &self,
+        response: &AccessTokenResponse,
+        user: &CurrentUser,
+        guilds: Option<&[CurrentUserGuild]>,
+    ) -> Result<String> {
+        self.sign(&self.claims(response, user, guilds))
+    }
+}
+
+/// A verifier of tokens issued by a [`JwtIssuer`].
+///
+/// # Examples
+///
+/// Verify tokens signed with an Ed25519 key, given only its public key:
+///
+/// ```rust
+/// extern crate chrono;
+/// extern crate ed25519_dalek;
+/// extern crate serenity_oauth;
+///
+/// use chrono::{TimeZone, Utc};
+/// use ed25519_dalek::SigningKey;
+/// use serenity_oauth::jwt::{
+///     JwtIssuer,
+///     JwtSigningKey,
+///     JwtVerifier,
+///     JwtVerifyingKey,
+///     SessionClaims,
+/// };
+///
+/// # fn main() {
+/// let signing_key = SigningKey::from_bytes(&[7; 32]);
+/// let public_key = JwtVerifyingKey::Ed25519(signing_key.verifying_key());
+///
+/// let issuer = JwtIssuer::new("gateway", JwtSigningKey::Ed25519(signing_key));
+/// let verifier = JwtVerifier::new("gateway", public_key);
+///
+/// let token = issuer.sign(&SessionClaims {
+///     aud: None,
+///     exp: 1700000900,
+///     guilds: Some(vec!["81384788765712384".to_owned()]),
+///     iat: 1700000000,
+///     iss: "gateway".to_owned(),
+///     scope: "identify guilds".to_owned(),
+///     sub: "80351110224678912".to_owned(),
+/// }).unwrap();
+///
+/// let now = Utc.timestamp_opt(1700000300, 0).unwrap();
+/// let claims = verifier.verify_at(&token, now).unwrap();
+/// assert_eq!(claims.guild_ids(), Some(vec![81384788765712384]));
+///
+/// // The token must not have expired.
+/// let later = Utc.timestamp_opt(1700001000, 0).unwrap();
+/// assert!(verifier.verify_at(&token, later).is_err());
+/// # }
+/// ```
+///
+/// [`JwtIssuer`]: struct.JwtIssuer.html
+#[derive(Clone)]
+pub struct JwtVerifier {
+    /// The expected audience of tokens, if any.
+    pub audience: Option<String>,
+    /// The expected issuer of tokens.
+    pub issuer: String,
+    /// The key to verify the signatures of tokens with, if their header does
+    /// not name the ID of a key.
+    pub key: JwtVerifyingKey,
+    /// Keys to verify the signatures of tokens with, by the ID of the key
+    /// named in their header, such as while rotating keys.
+    ///
+    /// Tokens naming the ID of a key not in these are rejected with
+    /// [`JwtError::UnknownKey`].
+    ///
+    /// [`JwtError::UnknownKey`]: enum.JwtError.html#variant.UnknownKey
+    pub keys: HashMap<String, JwtVerifyingKey>,
+    /// The number of seconds of clock skew tolerated when checking the expiry
+    /// of tokens.
+    ///
+    /// This defaults to [`DEFAULT_LEEWAY`].
+    ///
+    /// [`DEFAULT_LEEWAY`]: constant.DEFAULT_LEEWAY.html
+    pub leeway: i64,
+}
+
+impl JwtVerifier {
+    /// Creates a verifier of tokens issued by the given issuer, signed with
+    /// the given key.
+    pub fn new<S>(issuer: S, key: JwtVerifyingKey) -> Self
+        where S: Into<String> {
+        Self {
+            audience: None,
+            issuer: issuer.into(),
+            key,
+            keys: HashMap::new(),
+            leeway: DEFAULT_LEEWAY,
+        }
+    }
+
+    /// Verifies tokens whose header names the given key ID with the given
+    /// key.
+    ///
+    /// Tokens without a key ID are verified with the verifier's [`key`].
+    ///
+    /// # Examples
+    ///
+    /// Keep accepting tokens signed with the previous key while issuing tokens
+    /// with a new one:
+    ///
+    /// ```rust
+    /// use serenity_oauth::jwt::{
+    ///     JwtIssuer,
+    ///     JwtSigningKey,
+    ///     JwtVerifier,
+    ///     SessionClaims,
+    /// };
+    ///
+    /// let previous = JwtSigningKey::hmac(b"old secret".to_vec()).unwrap();
+    /// let current = JwtSigningKey::hmac(b"new secret".to_vec()).unwrap();
+    ///
+    /// let verifier = JwtVerifier::new("gateway", previous.verifying_key())
+    ///     .with_key("2", current.verifying_key());
+    ///
+    /// let mut issuer = JwtIssuer::new("gateway", current);
+    /// issuer.key_id = Some("2".to_owned());
+    ///
+    /// let claims = SessionClaims {
+    ///     aud: None,
+    ///     exp: i64::max_value(),
+    ///     guilds: None,
+    ///     iat: 1700000000,
+    ///     iss: "gateway".to_owned(),
+    ///     scope: "identify".to_owned(),
+    ///     sub: "80351110224678912".to_owned(),
+    /// };
+    /// let token = issuer.sign(&claims).unwrap();
+    ///
+    /// assert!(verifier.verify(&token).is_ok());
+    ///
+    /// // Tokens naming an unknown key ID are rejected.
+    /// issuer.key_id = Some("3".to_owned());
+    /// let token = issuer.sign(&claims).unwrap();
+    /// assert!(verifier.verify(&token).is_err());
+    /// ```
+    ///
+    /// [`key`]: #structfield.key
+    pub fn with_key<S>(mut self, key_id: S, key: JwtVerifyingKey) -> Self
+        where S: Into<String> {
+        self.keys.insert(key_id.into(), key);
+
+        self
+    }
+
+    /// Verifies a token, returning its claims.
+    pub fn verify(&self, token: &str) -> Result<SessionClaims> {
+        self.verify_at(token, Utc::now())
+    }
+
+    /// Verifies a token as of the given time, returning its claims.
+    ///
+    /// Refer to [`verify`] for more information.
+    ///
+    /// [`verify`]: #method.verify
+    pub fn verify_at(&self, token: &str, now: DateTime<Utc>)
+        -> Result<SessionClaims> {
+        let mut parts = token.split('.');
+
+        let (header, payload, signature) = match (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) {
+            (Some(header), Some(payload), Some(signature), None) => {
+                (header, payload, signature)
+            },
+            _ => return Err(invalid(JwtError::Malformed)),
+        };
+
+        let signed = &token[..header.len() + 1 + payload.len()];
+        let header: Header = decode_json(header)?;
+        let key = match header.kid {
+            Some(kid) => match self.keys.get(&kid) {
+                Some(key) => key,
+                None => return Err(invalid(JwtError::UnknownKey(kid))),
+            },
+            None => &self.key,
+        };
+
+        if header.alg != key.alg() {
+            return Err(invalid(JwtError::UnsupportedAlgorithm(header.alg)));
+        }
+
+        key.verify(signed.as_bytes(), &decode(signature)?)?;
+
+        let claims: SessionClaims = decode_json(payload)?;
+
+        if claims.iss != self.issuer {
+            return Err(invalid(JwtError::Issuer(claims.iss)));
+        }
+
+        if let Some(ref audience) = self.audience {
+            if claims.aud.as_ref() != Some(audience) {
+                return Err(invalid(JwtError::Audience));
+            }
+        }
+
+        if claims.exp.saturating_add(self.leeway) <= now.timestamp() {
+            return Err(invalid(JwtError::Expired));
+        }
+
+        Ok(claims)
+    }
+}
+
+/// Rejects empty HMAC secrets.
+fn check_secret(secret: &[u8]) -> Result<()> {
+    if secret.is_empty() {
+        Err(invalid(JwtError::EmptySecret))
+    } else {
+        Ok(())
+    }
+}
+
+/// The header of a JSON Web Token.
+#[derive(Deserialize, Serialize)]
+struct Header {
+    alg: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    typ: Option<String>,
+}
+
+fn decode(part: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD.decode(part)
+        .map_err(|_| invalid(JwtError::Malformed))
+}
+
+fn decode_json<T: DeserializeOwned>(part: &str) -> Result<T> {
+    serde_json::from_slice(&decode(part)?)
+        .map_err(|_| invalid(JwtError::Malformed))
+}
+
+fn encode_json<T: Serialize>(value: &T) -> Result<String> {
+    Ok(URL_SAFE_NO_PAD.encode(serde_json::to_vec(value)?))
+}
+
+fn invalid(error: JwtError) -> Error {
+    Error::Jwt(error)
+}
@@ -15,6 +15,11 @@
 //! produce a URL that can be used to redirect users to authorize an application
 //! with the [`Scope::Bot`] scope.
 //!
+//! The [`OAuthClient`] holds your application's credentials and configuration,
+//! and can be used to build authorization URLs and perform grants without
+//! repeating them on every call.
+//!
+//! [`OAuthClient`]: struct.OAuthClient.html
 //! [`Scope`]: enum.Scope.html
 //! [`Scope::Bot`]: enum.Scope.html#variant.Bot
 //! [`model`]: model/
@@ -25,21 +30,59 @@
 
 #[macro_use] extern crate serde_derive;
 
+#[cfg(feature = "actix")]
+extern crate actix_web;
+#[cfg(feature = "axum")]
+extern crate axum;
+extern crate base64;
+extern crate chrono;
+extern crate ed25519_dalek;
+#[cfg(any(
+    feature = "actix",
+    feature = "axum",
+    feature = "rocket",
+    feature = "tower",
+))]
+extern crate futures_util;
+extern crate hex;
+#[cfg(feature = "tower")]
+extern crate http;
 extern crate hyper;
 extern crate percent_encoding;
+extern crate ring;
+#[cfg(feature = "rocket")]
+extern crate rocket;
 extern crate serde;
 extern crate serde_json;
 extern crate serde_urlencoded;
 extern crate serenity_model;
+#[cfg(any(feature = "axum", feature = "tower"))]
+extern crate tokio;
+extern crate toml;
+#[cfg(feature = "tower")]
+extern crate tower;
 
 pub mod bridge;
+pub mod cache;
+pub mod config;
 pub mod constants;
+pub mod events;
+pub mod installation;
+pub mod integrations;
+pub mod jwt;
 pub mod model;
+pub mod oidc;
+pub mod seal;
+pub mod store;
 pub mod utils;
 
+mod client;
+mod endpoints;
 mod error;
 mod scope;
 
 pub use bridge::hyper::DiscordOAuthHyperRequester;
+pub use client::OAuthClient;
+pub use endpoints::Endpoints;
 pub use error::{Error, Result};
 pub use scope::Scope;
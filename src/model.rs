@@ -1,7 +1,93 @@
 //! A collection of models that can be deserialized from response bodies and
 //! serialized into request bodies.
 
-use serenity_model::{PartialGuild, Webhook};
+use chrono::{DateTime, Utc};
+use constants::BASE_CDN_URI;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use serde_json::{self, Error as JsonError, Value};
+use serenity_model::{PartialGuild, Permissions, Webhook};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use super::Scope;
+
+/// Implements conversions and (de)serialization for an enum represented by a
+/// string in Discord's API, with an `Other` variant for unknown values.
+macro_rules! enum_string {
+    ($name:ident { $($variant:ident = $value:expr,)* }) => {
+        impl<'a> From<&'a str> for $name {
+            fn from(value: &'a str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Other(other.to_owned()),
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                f.write_str(match *self {
+                    $($name::$variant => $value,)*
+                    $name::Other(ref other) => other,
+                })
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de> {
+                String::deserialize(deserializer).map(|value| {
+                    $name::from(&*value)
+                })
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer {
+                serializer.collect_str(self)
+            }
+        }
+    }
+}
+
+/// Implements conversions and (de)serialization for an enum represented by an
+/// integer in Discord's API, with an `Other` variant for unknown values.
+macro_rules! enum_number {
+    ($name:ident { $($variant:ident = $value:expr,)* }) => {
+        impl From<u8> for $name {
+            fn from(value: u8) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Other(other),
+                }
+            }
+        }
+
+        impl From<$name> for u8 {
+            fn from(value: $name) -> u8 {
+                match value {
+                    $($name::$variant => $value,)*
+                    $name::Other(other) => other,
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de> {
+                u8::deserialize(deserializer).map($name::from)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer {
+                serializer.serialize_u8(u8::from(*self))
+            }
+        }
+    }
+}
 
 /// Structure of data used as the body of a request to exchange the [`code`] for
 /// an access token.
@@ -73,6 +159,15 @@ pub struct AccessTokenResponse {
     pub access_token: String,
     /// The number of seconds until the access token expires.
     pub expires_in: u64,
+    /// The user's OpenID Connect ID token.
+    ///
+    /// This is only present if the [`Scope::OpenId`] scope was granted, and
+    /// can be validated with an [`IdTokenValidator`].
+    ///
+    /// [`IdTokenValidator`]: ../oidc/struct.IdTokenValidator.html
+    /// [`Scope::OpenId`]: ../enum.Scope.html#variant.OpenId
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
     /// The refresh token to use when the access token expires.
     pub refresh_token: String,
     /// The scope that is granted.
@@ -81,6 +176,308 @@ pub struct AccessTokenResponse {
     pub token_type: String,
 }
 
+/// Request for adding the user that authorized an access token to an existing
+/// group DM.
+///
+/// The access token must have been granted the [`Scope::GdmJoin`] scope.
+///
+/// [`Scope::GdmJoin`]: ../enum.Scope.html#variant.GdmJoin
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AddGroupDmRecipientRequest {
+    /// The user's access token.
+    pub access_token: String,
+    /// The nickname to give the user in the group DM.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nick: Option<String>,
+}
+
+/// Request for adding the user that authorized an access token to a guild.
+///
+/// The access token must have been granted the [`Scope::GuildsJoin`] scope.
+///
+/// [`Scope::GuildsJoin`]: ../enum.Scope.html#variant.GuildsJoin
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AddGuildMemberRequest {
+    /// The user's access token.
+    pub access_token: String,
+    /// Whether the user is deafened in voice channels.
+    ///
+    /// Requires your bot to have the "Deafen Members" permission.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deaf: Option<bool>,
+    /// Whether the user is muted in voice channels.
+    ///
+    /// Requires your bot to have the "Mute Members" permission.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mute: Option<bool>,
+    /// The nickname to give the user.
+    ///
+    /// Requires your bot to have the "Manage Nicknames" permission.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nick: Option<String>,
+    /// The IDs of the roles to give the user.
+    ///
+    /// Requires your bot to have the "Manage Roles" permission.
+    #[serde(skip_serializing_if = "Vec::is_empty", with = "snowflakes")]
+    pub roles: Vec<u64>,
+}
+
+impl AddGuildMemberRequest {
+    /// Creates a new request body for adding the user that authorized the
+    /// given access token to a guild, without any optional fields set.
+    ///
+    /// # Examples
+    ///
+    /// Add a user with a nickname:
+    ///
+    /// ```rust
+    /// use serenity_oauth::model::AddGuildMemberRequest;
+    ///
+    /// let mut request = AddGuildMemberRequest::new("user access token");
+    /// request.nick = Some("new member".to_owned());
+    /// ```
+    pub fn new<S: Into<String>>(access_token: S) -> Self {
+        Self {
+            access_token: access_token.into(),
+            deaf: None,
+            mute: None,
+            nick: None,
+            roles: Vec::new(),
+        }
+    }
+}
+
+/// The result of adding a user to a guild.
+#[derive(Clone, Debug)]
+pub enum AddGuildMemberResponse {
+    /// The user was added to the guild.
+    Added(GuildMember),
+    /// The user was already a member of the guild, and was left unchanged.
+    AlreadyMember,
+}
+
+/// Error response data returned by Discord's API when a request fails.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApiErrorResponse {
+    /// Discord's JSON error code.
+    ///
+    /// Refer to [Discord's documentation] for a list of error codes.
+    ///
+    /// [Discord's documentation]: https://discord.com/developers/docs/topics/opcodes-and-status-codes#json
+    pub code: u64,
+    /// A human-readable description of the error.
+    pub message: String,
+}
+
+/// Partial information about an application, as included in an
+/// [`AuthorizationInformation`].
+///
+/// [`AuthorizationInformation`]: struct.AuthorizationInformation.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Application {
+    /// Whether users other than the application's owner can add its bot to
+    /// guilds.
+    #[serde(default)]
+    pub bot_public: bool,
+    /// Whether the application's bot requires the full OAuth2 code grant
+    /// flow to be added to a guild.
+    #[serde(default)]
+    pub bot_require_code_grant: bool,
+    /// The description of the application.
+    #[serde(default)]
+    pub description: String,
+    /// The application's icon hash, if it has an icon.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// The ID of the application.
+    #[serde(with = "snowflake")]
+    pub id: u64,
+    /// The name of the application.
+    pub name: String,
+    /// The hex-encoded key used to verify interactions and webhook events
+    /// sent to the application.
+    #[serde(default)]
+    pub verify_key: Option<String>,
+}
+
+/// The data of a [`WebhookEventType::ApplicationAuthorized`] event, sent when
+/// a user adds your application to a guild or to their account.
+///
+/// [`WebhookEventType::ApplicationAuthorized`]: enum.WebhookEventType.html#variant.ApplicationAuthorized
+#[derive(Clone, Debug, Deserialize)]
+pub struct ApplicationAuthorizedEvent {
+    /// The guild your application was added to, if it was installed into a
+    /// guild.
+    #[serde(default)]
+    pub guild: Option<PartialGuild>,
+    /// Where your application was installed.
+    #[serde(default)]
+    pub integration_type: Option<IntegrationType>,
+    /// The scopes that were granted.
+    #[serde(default)]
+    pub scopes: Vec<Scope>,
+    /// The user that authorized your application.
+    pub user: User,
+}
+
+/// The data of a [`WebhookEventType::ApplicationDeauthorized`] event, sent
+/// when a user deauthorizes your application.
+///
+/// [`WebhookEventType::ApplicationDeauthorized`]: enum.WebhookEventType.html#variant.ApplicationDeauthorized
+#[derive(Clone, Debug, Deserialize)]
+pub struct ApplicationDeauthorizedEvent {
+    /// The user that deauthorized your application.
+    pub user: User,
+}
+
+/// The role connection of the user that authorized an access token, used by
+/// Linked Roles to determine which roles the user is eligible for.
+///
+/// The [`metadata`] values are compared against your application's
+/// [`ApplicationRoleConnectionMetadata`] records, keyed by the records' keys.
+///
+/// # Examples
+///
+/// Create a role connection for a user with a verified account on your
+/// platform:
+///
+/// ```rust
+/// use serenity_oauth::model::ApplicationRoleConnection;
+///
+/// let mut connection = ApplicationRoleConnection::default();
+/// connection.platform_name = Some("My Platform".to_owned());
+/// connection.set_boolean("verified", true);
+/// connection.set_integer("games_played", 42);
+///
+/// assert_eq!(connection.metadata["verified"], "1");
+/// assert_eq!(connection.metadata["games_played"], "42");
+/// ```
+///
+/// [`ApplicationRoleConnectionMetadata`]: struct.ApplicationRoleConnectionMetadata.html
+/// [`metadata`]: #structfield.metadata
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ApplicationRoleConnection {
+    /// The metadata values of the user, keyed by metadata record key.
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// The vanity name of your platform, shown on the user's profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform_name: Option<String>,
+    /// The user's username on your platform, shown on the user's profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform_username: Option<String>,
+}
+
+impl ApplicationRoleConnection {
+    /// Sets a metadata value for a record with a boolean type.
+    pub fn set_boolean<S: Into<String>>(&mut self, key: S, value: bool) {
+        let value = if value { "1" } else { "0" };
+
+        self.metadata.insert(key.into(), value.to_owned());
+    }
+
+    /// Sets a metadata value for a record with a datetime type.
+    pub fn set_datetime<S>(&mut self, key: S, value: DateTime<Utc>)
+        where S: Into<String> {
+        self.metadata.insert(key.into(), value.to_rfc3339());
+    }
+
+    /// Sets a metadata value for a record with an integer type.
+    pub fn set_integer<S: Into<String>>(&mut self, key: S, value: i64) {
+        self.metadata.insert(key.into(), value.to_string());
+    }
+}
+
+/// A metadata record of your application, describing a requirement that guild
+/// administrators can use when configuring Linked Roles.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApplicationRoleConnectionMetadata {
+    /// The description of the record, up to 200 characters.
+    pub description: String,
+    /// Translations of the description, keyed by locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description_localizations: Option<HashMap<String, String>>,
+    /// The key of the record, made of up to 50 lowercase letters, digits, and
+    /// underscores.
+    pub key: String,
+    /// The type of comparison made against the user's metadata value.
+    #[serde(rename = "type")]
+    pub kind: RoleConnectionMetadataType,
+    /// The name of the record, up to 100 characters.
+    pub name: String,
+    /// Translations of the name, keyed by locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_localizations: Option<HashMap<String, String>>,
+}
+
+impl ApplicationRoleConnectionMetadata {
+    /// Creates a new metadata record without any translations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_oauth::model::{
+    ///     ApplicationRoleConnectionMetadata,
+    ///     RoleConnectionMetadataType,
+    /// };
+    ///
+    /// let record = ApplicationRoleConnectionMetadata::new(
+    ///     RoleConnectionMetadataType::IntegerGreaterThanOrEqual,
+    ///     "games_played",
+    ///     "Games Played",
+    ///     "Minimum number of games played",
+    /// );
+    /// ```
+    pub fn new<S, T, U>(
+        kind: RoleConnectionMetadataType,
+        key: S,
+        name: T,
+        description: U,
+    ) -> Self where S: Into<String>, T: Into<String>, U: Into<String> {
+        Self {
+            description: description.into(),
+            description_localizations: None,
+            key: key.into(),
+            name: name.into(),
+            name_localizations: None,
+            kind,
+        }
+    }
+}
+
+/// Information about the authorization of an access token, as returned by
+/// [`DiscordOAuthHyperRequester::current_authorization`].
+///
+/// [`DiscordOAuthHyperRequester::current_authorization`]: ../bridge/hyper/trait.DiscordOAuthHyperRequester.html#method.current_authorization
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuthorizationInformation {
+    /// The application the access token was granted to.
+    pub application: Application,
+    /// When the access token expires.
+    pub expires: DateTime<Utc>,
+    /// The scopes granted to the access token.
+    pub scopes: Vec<Scope>,
+    /// The user that authorized the access token.
+    ///
+    /// This is only present if the [`Scope::Identify`] scope was granted.
+    ///
+    /// [`Scope::Identify`]: ../enum.Scope.html#variant.Identify
+    #[serde(default)]
+    pub user: Option<User>,
+}
+
+impl AuthorizationInformation {
+    /// Whether the given scope was granted to the access token.
+    pub fn has_scope(&self, scope: &Scope) -> bool {
+        self.scopes.contains(scope)
+    }
+
+    /// Whether the access token has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires <= Utc::now()
+    }
+}
+
 /// Response data containing an access token, but without a refresh token.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ClientCredentialsAccessTokenResponse {
@@ -94,6 +491,569 @@ pub struct ClientCredentialsAccessTokenResponse {
     pub token_type: String,
 }
 
+/// Request for retrieving an access token for the application owner using the
+/// client credentials grant.
+///
+/// This is useful for testing and for tooling used by the owner of the
+/// application, as no user needs to authorize the application.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ClientCredentialsRequest {
+    /// Your application's client ID.
+    pub client_id: u64,
+    /// Your application's client secret.
+    pub client_secret: String,
+    /// The type of grant.
+    ///
+    /// Must be set to `client_credentials`.
+    ///
+    /// If using [`ClientCredentialsRequest::new`], this will automatically be
+    /// set for you.
+    pub grant_type: String,
+    /// The space-separated list of scopes to request.
+    pub scope: String,
+}
+
+impl ClientCredentialsRequest {
+    /// Creates a new request body for retrieving an access token using the
+    /// client credentials grant.
+    ///
+    /// # Examples
+    ///
+    /// Create a new request and assert that the grant type and scopes are
+    /// correct:
+    ///
+    /// ```rust
+    /// use serenity_oauth::model::ClientCredentialsRequest;
+    /// use serenity_oauth::Scope;
+    ///
+    /// let request = ClientCredentialsRequest::new(
+    ///     249608697955745802,
+    ///     "dd99opUAgs7SQEtk2kdRrTMU5zagR2a4",
+    ///     &[Scope::Identify, Scope::Connections],
+    /// );
+    ///
+    /// assert_eq!(request.grant_type, "client_credentials");
+    /// assert_eq!(request.scope, "identify connections");
+    /// ```
+    pub fn new<S>(client_id: u64, client_secret: S, scopes: &[Scope]) -> Self
+        where S: Into<String> {
+        Self {
+            client_secret: client_secret.into(),
+            grant_type: "client_credentials".to_owned(),
+            scope: Scope::join_list(scopes),
+            client_id,
+        }
+    }
+}
+
+/// A third-party account linked to the user that authorized an access token.
+///
+/// This is received from the `/users/@me/connections` endpoint, and requires
+/// the [`Scope::Connections`] scope.
+///
+/// [`Scope::Connections`]: ../enum.Scope.html#variant.Connections
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Connection {
+    /// Whether friend sync is enabled for the connection.
+    #[serde(default)]
+    pub friend_sync: bool,
+    /// The ID of the account on the third-party service.
+    pub id: String,
+    /// The guild integrations attached to the connection.
+    #[serde(default)]
+    pub integrations: Vec<ConnectionIntegration>,
+    /// The third-party service that the account belongs to.
+    #[serde(rename = "type")]
+    pub kind: ConnectionType,
+    /// The username of the account on the third-party service.
+    pub name: String,
+    /// Whether the connection has been revoked.
+    #[serde(default)]
+    pub revoked: bool,
+    /// Whether activities related to the connection are shown in the user's
+    /// presence.
+    #[serde(default)]
+    pub show_activity: bool,
+    /// Whether the connection has been verified.
+    #[serde(default)]
+    pub verified: bool,
+    /// Who can see the connection.
+    pub visibility: ConnectionVisibility,
+}
+
+/// A guild integration attached to a [`Connection`], such as a Twitch
+/// subscriber role.
+///
+/// [`Connection`]: struct.Connection.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ConnectionIntegration {
+    /// The account that the integration belongs to.
+    #[serde(default)]
+    pub account: Option<ConnectionIntegrationAccount>,
+    /// Whether the integration is enabled.
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// The ID of the integration.
+    #[serde(with = "snowflake")]
+    pub id: u64,
+    /// The type of integration, such as `twitch` or `youtube`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The name of the integration.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// The account on a third-party service that a [`ConnectionIntegration`]
+/// belongs to.
+///
+/// [`ConnectionIntegration`]: struct.ConnectionIntegration.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ConnectionIntegrationAccount {
+    /// The ID of the account.
+    pub id: String,
+    /// The name of the account.
+    pub name: String,
+}
+
+/// The third-party service that a [`Connection`] belongs to.
+///
+/// If a service is not registered here, [`ConnectionType::Other`] is used.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_oauth::model::ConnectionType;
+///
+/// assert_eq!(ConnectionType::from("github"), ConnectionType::GitHub);
+/// assert_eq!(
+///     ConnectionType::from("mastodon"),
+///     ConnectionType::Other("mastodon".to_owned()),
+/// );
+/// ```
+///
+/// [`Connection`]: struct.Connection.html
+/// [`ConnectionType::Other`]: #variant.Other
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ConnectionType {
+    /// A Battle.net account.
+    BattleNet,
+    /// A Bungie.net account.
+    Bungie,
+    /// A Crunchyroll account.
+    Crunchyroll,
+    /// A verified domain.
+    Domain,
+    /// An eBay account.
+    Ebay,
+    /// An Epic Games account.
+    EpicGames,
+    /// A Facebook account.
+    Facebook,
+    /// A GitHub account.
+    GitHub,
+    /// An Instagram account.
+    Instagram,
+    /// A League of Legends account.
+    LeagueOfLegends,
+    /// A PayPal account.
+    PayPal,
+    /// A PlayStation Network account.
+    PlayStation,
+    /// A Reddit account.
+    Reddit,
+    /// A Riot Games account.
+    RiotGames,
+    /// A Roblox account.
+    Roblox,
+    /// A Skype account.
+    Skype,
+    /// A Spotify account.
+    Spotify,
+    /// A Steam account.
+    Steam,
+    /// A TikTok account.
+    TikTok,
+    /// A Twitch account.
+    Twitch,
+    /// A Twitter (X) account.
+    Twitter,
+    /// An Xbox account.
+    Xbox,
+    /// A YouTube account.
+    YouTube,
+    /// A service that does not have a matching enum variant.
+    Other(String),
+}
+
+enum_string!(ConnectionType {
+    BattleNet = "battlenet",
+    Bungie = "bungie",
+    Crunchyroll = "crunchyroll",
+    Domain = "domain",
+    Ebay = "ebay",
+    EpicGames = "epicgames",
+    Facebook = "facebook",
+    GitHub = "github",
+    Instagram = "instagram",
+    LeagueOfLegends = "leagueoflegends",
+    PayPal = "paypal",
+    PlayStation = "playstation",
+    Reddit = "reddit",
+    RiotGames = "riotgames",
+    Roblox = "roblox",
+    Skype = "skype",
+    Spotify = "spotify",
+    Steam = "steam",
+    TikTok = "tiktok",
+    Twitch = "twitch",
+    Twitter = "twitter",
+    Xbox = "xbox",
+    YouTube = "youtube",
+});
+
+/// Who can see a [`Connection`].
+///
+/// [`Connection`]: struct.Connection.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ConnectionVisibility {
+    /// The connection is only visible to the user.
+    None,
+    /// The connection is visible to everyone.
+    Everyone,
+    /// A visibility that does not have a matching enum variant.
+    Other(u8),
+}
+
+enum_number!(ConnectionVisibility {
+    None = 0,
+    Everyone = 1,
+});
+
+/// Request for creating a group DM from the access tokens of its members.
+///
+/// Each access token must have been granted the [`Scope::GdmJoin`] scope.
+///
+/// # Examples
+///
+/// Create a request for a group DM with two users, giving one a nickname:
+///
+/// ```rust
+/// use serenity_oauth::model::CreateGroupDmRequest;
+///
+/// let mut request = CreateGroupDmRequest::new(vec![
+///     "first user access token".to_owned(),
+///     "second user access token".to_owned(),
+/// ]);
+/// request.nicks.insert(114941315417899012, "organizer".to_owned());
+/// ```
+///
+/// [`Scope::GdmJoin`]: ../enum.Scope.html#variant.GdmJoin
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CreateGroupDmRequest {
+    /// The access tokens of the users to add to the group DM.
+    pub access_tokens: Vec<String>,
+    /// Nicknames to give users in the group DM, keyed by user ID.
+    #[serde(default)]
+    pub nicks: HashMap<u64, String>,
+}
+
+impl CreateGroupDmRequest {
+    /// Creates a new request body for creating a group DM with the users that
+    /// authorized the given access tokens, without any nicknames.
+    pub fn new(access_tokens: Vec<String>) -> Self {
+        Self {
+            nicks: HashMap::new(),
+            access_tokens,
+        }
+    }
+}
+
+/// Information about the user that authorized an access token.
+///
+/// This is received from the `/users/@me` endpoint, and requires the
+/// [`Scope::Identify`] scope. The [`email`] is only present if the
+/// [`Scope::Email`] scope was granted.
+///
+/// [`Scope::Email`]: ../enum.Scope.html#variant.Email
+/// [`Scope::Identify`]: ../enum.Scope.html#variant.Identify
+/// [`email`]: #structfield.email
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CurrentUser {
+    /// The user's avatar hash, if they have an avatar.
+    #[serde(default)]
+    pub avatar: Option<String>,
+    /// Whether the user is a bot.
+    #[serde(default)]
+    pub bot: bool,
+    /// The user's 4-digit discriminator.
+    ///
+    /// This is `"0"` for users that have migrated to unique usernames.
+    pub discriminator: String,
+    /// The user's email address.
+    ///
+    /// This is only present if the [`Scope::Email`] scope was granted.
+    ///
+    /// [`Scope::Email`]: ../enum.Scope.html#variant.Email
+    #[serde(default)]
+    pub email: Option<String>,
+    /// The flags on the user's account.
+    #[serde(default)]
+    pub flags: Option<u64>,
+    /// The user's display name, if they have set one.
+    #[serde(default)]
+    pub global_name: Option<String>,
+    /// The user's ID.
+    #[serde(with = "snowflake")]
+    pub id: u64,
+    /// The user's chosen language option.
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Whether the user has two factor authentication enabled.
+    #[serde(default)]
+    pub mfa_enabled: Option<bool>,
+    /// The type of Nitro subscription on the user's account.
+    #[serde(default)]
+    pub premium_type: Option<PremiumType>,
+    /// The public flags on the user's account.
+    #[serde(default)]
+    pub public_flags: Option<u64>,
+    /// The user's username.
+    pub username: String,
+    /// Whether the user's email address has been verified.
+    ///
+    /// This is only present if the [`Scope::Email`] scope was granted.
+    ///
+    /// [`Scope::Email`]: ../enum.Scope.html#variant.Email
+    #[serde(default)]
+    pub verified: Option<bool>,
+}
+
+impl CurrentUser {
+    /// Produces the URL of the user's avatar, if they have one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate serde_json;
+    /// extern crate serenity_oauth;
+    ///
+    /// # fn main() {
+    /// use serenity_oauth::model::CurrentUser;
+    ///
+    /// let user: CurrentUser = serde_json::from_str(r#"{
+    ///     "id": "80351110224678912",
+    ///     "username": "nelly",
+    ///     "discriminator": "1337",
+    ///     "avatar": "8342729096ea3675442027381ff50dfe"
+    /// }"#).unwrap();
+    ///
+    /// let expected = "https://cdn.discordapp.com/avatars/80351110224678912/8342729096ea3675442027381ff50dfe.png";
+    /// assert_eq!(user.avatar_url().unwrap(), expected);
+    /// # }
+    /// ```
+    pub fn avatar_url(&self) -> Option<String> {
+        self.avatar.as_ref().map(|avatar| {
+            let ext = if avatar.starts_with("a_") { "gif" } else { "png" };
+
+            format!("{}/avatars/{}/{}.{}", BASE_CDN_URI, self.id, avatar, ext)
+        })
+    }
+}
+
+/// Basic information about a guild the user that authorized an access token
+/// is a member of.
+///
+/// This is received from the `/users/@me/guilds` endpoint, and requires the
+/// [`Scope::Guilds`] scope.
+///
+/// [`Scope::Guilds`]: ../enum.Scope.html#variant.Guilds
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CurrentUserGuild {
+    /// The enabled features of the guild.
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// The guild's icon hash, if it has an icon.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// The guild's ID.
+    #[serde(with = "snowflake")]
+    pub id: u64,
+    /// The name of the guild.
+    pub name: String,
+    /// Whether the user is the owner of the guild.
+    #[serde(default)]
+    pub owner: bool,
+    /// The user's total permissions in the guild, excluding channel overwrites.
+    #[serde(with = "permissions")]
+    pub permissions: Permissions,
+}
+
+impl CurrentUserGuild {
+    /// Produces the URL of the guild's icon, if it has one.
+    pub fn icon_url(&self) -> Option<String> {
+        self.icon.as_ref().map(|icon| {
+            let ext = if icon.starts_with("a_") { "gif" } else { "png" };
+
+            format!("{}/icons/{}/{}.{}", BASE_CDN_URI, self.id, icon, ext)
+        })
+    }
+}
+
+/// Query parameters for paginating through the guilds of the user that
+/// authorized an access token.
+///
+/// Guilds are sorted by ID. Only one of [`after`] and [`before`] should be
+/// given.
+///
+/// [`after`]: #structfield.after
+/// [`before`]: #structfield.before
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CurrentUserGuildsQuery {
+    /// Retrieve guilds with an ID after this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<u64>,
+    /// Retrieve guilds with an ID before this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<u64>,
+    /// The maximum number of guilds to retrieve, from 1 to 200.
+    ///
+    /// Discord defaults to 200.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u8>,
+}
+
+/// An OpenID Connect discovery document, describing the provider's endpoints
+/// and supported features.
+///
+/// Discord serves its discovery document at [`oidc::DISCOVERY_URL`].
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate serde_json;
+/// extern crate serenity_oauth;
+///
+/// use serenity_oauth::model::DiscoveryDocument;
+/// use serenity_oauth::Scope;
+///
+/// # fn main() {
+/// let document: DiscoveryDocument = serde_json::from_str(r#"{
+///     "issuer": "https://discord.com",
+///     "authorization_endpoint": "https://discord.com/oauth2/authorize",
+///     "token_endpoint": "https://discord.com/api/oauth2/token",
+///     "jwks_uri": "https://discord.com/api/oauth2/keys",
+///     "scopes_supported": ["openid", "identify", "email"],
+///     "id_token_signing_alg_values_supported": ["RS256"]
+/// }"#).unwrap();
+///
+/// assert_eq!(document.jwks_uri, "https://discord.com/api/oauth2/keys");
+/// assert!(document.scopes_supported.contains(&Scope::OpenId));
+/// # }
+/// ```
+///
+/// [`oidc::DISCOVERY_URL`]: ../oidc/constant.DISCOVERY_URL.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DiscoveryDocument {
+    /// The URL of the authorization endpoint.
+    pub authorization_endpoint: String,
+    /// The claims that can be included in ID tokens.
+    #[serde(default)]
+    pub claims_supported: Vec<String>,
+    /// The signing algorithms used for ID tokens.
+    #[serde(default)]
+    pub id_token_signing_alg_values_supported: Vec<String>,
+    /// The issuer identifier, which ID tokens' `iss` claim must match.
+    pub issuer: String,
+    /// The URL of the JSON Web Key Set used to sign ID tokens.
+    pub jwks_uri: String,
+    /// The response types that are supported.
+    #[serde(default)]
+    pub response_types_supported: Vec<String>,
+    /// The URL of the token revocation endpoint, if any.
+    #[serde(default)]
+    pub revocation_endpoint: Option<String>,
+    /// The scopes that are supported.
+    #[serde(default)]
+    pub scopes_supported: Vec<Scope>,
+    /// The subject identifier types that are supported.
+    #[serde(default)]
+    pub subject_types_supported: Vec<String>,
+    /// The URL of the token endpoint.
+    pub token_endpoint: String,
+    /// The URL of the UserInfo endpoint, if any.
+    #[serde(default)]
+    pub userinfo_endpoint: Option<String>,
+}
+
+/// Request for editing a message previously sent by a webhook.
+///
+/// Only the fields that are set are changed.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct EditWebhookMessageRequest {
+    /// The new content of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// The new embeds of the message, as embed objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Value>>,
+}
+
+/// Request for sending a message through a webhook.
+///
+/// At least one of [`content`] and [`embeds`] must be set.
+///
+/// # Examples
+///
+/// Send a message with an embed, overriding the webhook's username:
+///
+/// ```rust
+/// #[macro_use] extern crate serde_json;
+/// extern crate serenity_oauth;
+///
+/// use serenity_oauth::model::ExecuteWebhookRequest;
+///
+/// # fn main() {
+/// let mut request = ExecuteWebhookRequest::new("A new release is out!");
+/// request.username = Some("Release Bot".to_owned());
+/// request.embeds.push(json!({
+///     "title": "v0.2.0",
+///     "url": "https://myapplication.website/releases/0.2.0",
+/// }));
+/// # }
+/// ```
+///
+/// [`content`]: #structfield.content
+/// [`embeds`]: #structfield.embeds
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ExecuteWebhookRequest {
+    /// The URL of an avatar to use instead of the webhook's default avatar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    /// The content of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// The embeds of the message, as embed objects.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub embeds: Vec<Value>,
+    /// Whether the message is sent as a text-to-speech message.
+    #[serde(default)]
+    pub tts: bool,
+    /// The username to use instead of the webhook's default username.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+}
+
+impl ExecuteWebhookRequest {
+    /// Creates a new request for sending a message with the given content.
+    pub fn new<S: Into<String>>(content: S) -> Self {
+        Self {
+            content: Some(content.into()),
+            ..Self::default()
+        }
+    }
+}
+
 /// An extended [`Scope::Bot`] authorization flow.
 ///
 /// This will authorize the application as a bot into a user's selected guild,
@@ -116,6 +1076,282 @@ pub struct ExtendedBotAuthorizationResponse {
     pub token_type: String,
 }
 
+/// The response data from exchanging a code for an access token, for any kind
+/// of authorization flow.
+///
+/// Alongside the access token, this includes the guild the application's bot
+/// was added to if the [`Scope::Bot`] scope was authorized, and the created
+/// webhook if the [`Scope::WebhookIncoming`] scope was authorized.
+///
+/// [`Scope::Bot`]: ../enum.Scope.html#variant.Bot
+/// [`Scope::WebhookIncoming`]: ../enum.Scope.html#variant.WebhookIncoming
+#[derive(Clone, Debug, Deserialize)]
+pub struct Grant {
+    /// The user's access token.
+    pub access_token: String,
+    /// The number of seconds until the access token expires.
+    pub expires_in: u64,
+    /// Partial guild data that the application's bot was authorized into, if
+    /// any.
+    #[serde(default)]
+    pub guild: Option<PartialGuild>,
+    /// The user's OpenID Connect ID token, if the [`Scope::OpenId`] scope was
+    /// granted.
+    ///
+    /// [`Scope::OpenId`]: ../enum.Scope.html#variant.OpenId
+    #[serde(default)]
+    pub id_token: Option<String>,
+    /// The permissions granted to the application's bot in the [`guild`], if
+    /// any.
+    ///
+    /// Discord passes these in the `permissions` query parameter of the
+    /// redirect rather than in the token response, so they must be set from
+    /// there.
+    ///
+    /// [`guild`]: #structfield.guild
+    #[serde(default, deserialize_with = "option_permissions::deserialize")]
+    pub permissions: Option<Permissions>,
+    /// The refresh token to use when the access token expires.
+    pub refresh_token: String,
+    /// The scope that is granted.
+    pub scope: String,
+    /// The type of token received.
+    pub token_type: String,
+    /// Information about the webhook created, if any.
+    #[serde(default)]
+    pub webhook: Option<Webhook>,
+}
+
+impl Grant {
+    /// Parses the scopes that were granted.
+    pub fn scopes(&self) -> Vec<Scope> {
+        Scope::parse_list(&self.scope)
+    }
+
+    /// The ID and token of the created webhook, if any, which should be stored
+    /// to execute it later.
+    pub fn webhook_credentials(&self) -> Option<WebhookCredentials> {
+        self.webhook.as_ref().map(WebhookCredentials::from)
+    }
+}
+
+impl From<Grant> for AccessTokenResponse {
+    fn from(grant: Grant) -> Self {
+        Self {
+            access_token: grant.access_token,
+            expires_in: grant.expires_in,
+            id_token: grant.id_token,
+            refresh_token: grant.refresh_token,
+            scope: grant.scope,
+            token_type: grant.token_type,
+        }
+    }
+}
+
+/// A group DM channel.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GroupDmChannel {
+    /// The ID of the application that created the group DM, if any.
+    #[serde(default, with = "option_snowflake")]
+    pub application_id: Option<u64>,
+    /// The group DM's icon hash, if it has an icon.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// The ID of the group DM.
+    #[serde(with = "snowflake")]
+    pub id: u64,
+    /// The name of the group DM, if it has one.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The ID of the user that owns the group DM, if any.
+    #[serde(default, with = "option_snowflake")]
+    pub owner_id: Option<u64>,
+    /// The members of the group DM.
+    #[serde(default)]
+    pub recipients: Vec<User>,
+}
+
+/// Information about a user's membership of a guild.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildMember {
+    /// The member's guild-specific avatar hash, if they have one.
+    #[serde(default)]
+    pub avatar: Option<String>,
+    /// When the member's timeout will expire, if they are timed out.
+    #[serde(default)]
+    pub communication_disabled_until: Option<DateTime<Utc>>,
+    /// Whether the member is deafened in voice channels.
+    #[serde(default)]
+    pub deaf: bool,
+    /// When the user joined the guild.
+    pub joined_at: DateTime<Utc>,
+    /// Whether the member is muted in voice channels.
+    #[serde(default)]
+    pub mute: bool,
+    /// The member's nickname, if they have one.
+    #[serde(default)]
+    pub nick: Option<String>,
+    /// Whether the member has not yet passed the guild's membership screening
+    /// requirements.
+    #[serde(default)]
+    pub pending: bool,
+    /// When the member started boosting the guild, if they are boosting it.
+    #[serde(default)]
+    pub premium_since: Option<DateTime<Utc>>,
+    /// The IDs of the member's roles.
+    #[serde(default, with = "snowflakes")]
+    pub roles: Vec<u64>,
+    /// The user the member represents.
+    #[serde(default)]
+    pub user: Option<User>,
+}
+
+impl GuildMember {
+    /// Whether the member has the role with the given ID.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate serde_json;
+    /// extern crate serenity_oauth;
+    ///
+    /// # fn main() {
+    /// use serenity_oauth::model::GuildMember;
+    ///
+    /// let member: GuildMember = serde_json::from_str(r#"{
+    ///     "joined_at": "2015-04-26T06:26:56.936000+00:00",
+    ///     "roles": ["41771983423143936"]
+    /// }"#).unwrap();
+    ///
+    /// assert!(member.has_role(41771983423143936));
+    /// assert!(!member.has_role(41771983423143937));
+    /// # }
+    /// ```
+    pub fn has_role(&self, role_id: u64) -> bool {
+        self.roles.contains(&role_id)
+    }
+
+    /// Whether the member is currently timed out, and unable to communicate in
+    /// the guild.
+    pub fn is_timed_out(&self) -> bool {
+        self.communication_disabled_until
+            .map_or(false, |until| until > Utc::now())
+    }
+}
+
+/// The claims of a validated OpenID Connect ID token.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IdTokenClaims {
+    /// The hash of the access token issued alongside the ID token, if any.
+    #[serde(default)]
+    pub at_hash: Option<String>,
+    /// The audiences the ID token is intended for, which includes your
+    /// application's client ID.
+    #[serde(with = "string_or_vec")]
+    pub aud: Vec<String>,
+    /// When the user authenticated, as a Unix timestamp.
+    #[serde(default)]
+    pub auth_time: Option<i64>,
+    /// When the ID token expires, as a Unix timestamp.
+    pub exp: i64,
+    /// When the ID token was issued, as a Unix timestamp.
+    pub iat: i64,
+    /// The issuer of the ID token.
+    pub iss: String,
+    /// The nonce passed in the authorization URL, if any.
+    #[serde(default)]
+    pub nonce: Option<String>,
+    /// The subject of the ID token, which is the user's ID.
+    pub sub: String,
+}
+
+impl IdTokenClaims {
+    /// Parses the user's ID from the [`sub`] claim.
+    ///
+    /// [`sub`]: #structfield.sub
+    pub fn user_id(&self) -> Option<u64> {
+        self.sub.parse().ok()
+    }
+}
+
+/// Where an application was installed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IntegrationType {
+    /// The application was installed into a guild.
+    GuildInstall,
+    /// The application was installed to a user's account.
+    UserInstall,
+    /// An integration type that does not have a matching enum variant.
+    Other(u8),
+}
+
+enum_number!(IntegrationType {
+    GuildInstall = 0,
+    UserInstall = 1,
+});
+
+/// A JSON Web Key, as found in a [`Jwks`].
+///
+/// [`Jwks`]: struct.Jwks.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Jwk {
+    /// The algorithm the key is used with, if given.
+    #[serde(default)]
+    pub alg: Option<String>,
+    /// The exponent of an RSA key, base64url-encoded.
+    #[serde(default)]
+    pub e: Option<String>,
+    /// The ID of the key, if given.
+    #[serde(default)]
+    pub kid: Option<String>,
+    /// The type of the key, such as `RSA`.
+    pub kty: String,
+    /// The modulus of an RSA key, base64url-encoded.
+    #[serde(default)]
+    pub n: Option<String>,
+    /// The intended use of the key, such as `sig`, if given.
+    #[serde(default, rename = "use")]
+    pub usage: Option<String>,
+}
+
+/// A JSON Web Key Set, containing the keys used to sign ID tokens.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Jwks {
+    /// The keys in the set.
+    pub keys: Vec<Jwk>,
+}
+
+impl Jwks {
+    /// Finds the key with the given ID.
+    pub fn find(&self, kid: &str) -> Option<&Jwk> {
+        self.keys.iter().find(|key| {
+            key.kid.as_ref().map(|id| &**id) == Some(kid)
+        })
+    }
+}
+
+/// The type of Nitro subscription on a user's account.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PremiumType {
+    /// The user does not have a subscription.
+    None,
+    /// The user has a Nitro Classic subscription.
+    NitroClassic,
+    /// The user has a Nitro subscription.
+    Nitro,
+    /// The user has a Nitro Basic subscription.
+    NitroBasic,
+    /// A subscription type that does not have a matching enum variant.
+    Other(u8),
+}
+
+enum_number!(PremiumType {
+    None = 0,
+    NitroClassic = 1,
+    Nitro = 2,
+    NitroBasic = 3,
+});
+
 /// Request for exchanging a refresh token for a new access token.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RefreshTokenRequest {
@@ -172,6 +1408,356 @@ impl RefreshTokenRequest {
     }
 }
 
+/// The type of comparison that an [`ApplicationRoleConnectionMetadata`] record
+/// makes against a user's metadata value.
+///
+/// [`ApplicationRoleConnectionMetadata`]: struct.ApplicationRoleConnectionMetadata.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RoleConnectionMetadataType {
+    /// The user's integer is less than or equal to the guild's configured
+    /// value.
+    IntegerLessThanOrEqual,
+    /// The user's integer is greater than or equal to the guild's configured
+    /// value.
+    IntegerGreaterThanOrEqual,
+    /// The user's integer is equal to the guild's configured value.
+    IntegerEqual,
+    /// The user's integer is not equal to the guild's configured value.
+    IntegerNotEqual,
+    /// The user's datetime is less than or equal to the guild's configured
+    /// number of days before the current date.
+    DatetimeLessThanOrEqual,
+    /// The user's datetime is greater than or equal to the guild's configured
+    /// number of days before the current date.
+    DatetimeGreaterThanOrEqual,
+    /// The user's boolean is equal to the guild's configured value.
+    BooleanEqual,
+    /// The user's boolean is not equal to the guild's configured value.
+    BooleanNotEqual,
+    /// A type that does not have a matching enum variant.
+    Other(u8),
+}
+
+enum_number!(RoleConnectionMetadataType {
+    IntegerLessThanOrEqual = 1,
+    IntegerGreaterThanOrEqual = 2,
+    IntegerEqual = 3,
+    IntegerNotEqual = 4,
+    DatetimeLessThanOrEqual = 5,
+    DatetimeGreaterThanOrEqual = 6,
+    BooleanEqual = 7,
+    BooleanNotEqual = 8,
+});
+
+/// Response data returned by the token endpoint when a grant could not be
+/// exchanged.
+///
+/// The [`error`] is one of the error codes defined by [RFC 6749 section 5.2],
+/// such as `invalid_grant` when a refresh token has been revoked or has
+/// expired.
+///
+/// [RFC 6749 section 5.2]: https://tools.ietf.org/html/rfc6749#section-5.2
+/// [`error`]: #structfield.error
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TokenErrorResponse {
+    /// The error code of the failure.
+    pub error: String,
+    /// A human-readable description of the failure, if one was given.
+    #[serde(default)]
+    pub error_description: Option<String>,
+}
+
+impl TokenErrorResponse {
+    /// Whether the grant has permanently been invalidated.
+    ///
+    /// This is the case when the user has deauthorized the application, or the
+    /// refresh token has been revoked or has expired. Retrying the request will
+    /// never succeed, and the user will need to authorize the application
+    /// again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_oauth::model::TokenErrorResponse;
+    ///
+    /// let response = TokenErrorResponse {
+    ///     error: "invalid_grant".to_owned(),
+    ///     error_description: None,
+    /// };
+    ///
+    /// assert!(response.is_revoked());
+    /// ```
+    pub fn is_revoked(&self) -> bool {
+        self.error == "invalid_grant"
+    }
+
+    /// Whether the token endpoint failed temporarily, and the request may
+    /// succeed if retried later.
+    ///
+    /// This is the case for the `server_error` and `temporarily_unavailable`
+    /// error codes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_oauth::model::TokenErrorResponse;
+    ///
+    /// let response = TokenErrorResponse {
+    ///     error: "temporarily_unavailable".to_owned(),
+    ///     error_description: None,
+    /// };
+    ///
+    /// assert!(response.is_transient());
+    /// assert!(!response.is_revoked());
+    /// ```
+    pub fn is_transient(&self) -> bool {
+        self.error == "server_error" || self.error == "temporarily_unavailable"
+    }
+}
+
+/// Public information about a user.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct User {
+    /// The user's avatar hash, if they have an avatar.
+    #[serde(default)]
+    pub avatar: Option<String>,
+    /// Whether the user is a bot.
+    #[serde(default)]
+    pub bot: bool,
+    /// The user's 4-digit discriminator.
+    ///
+    /// This is `"0"` for users that have migrated to unique usernames.
+    pub discriminator: String,
+    /// The user's display name, if they have set one.
+    #[serde(default)]
+    pub global_name: Option<String>,
+    /// The user's ID.
+    #[serde(with = "snowflake")]
+    pub id: u64,
+    /// The user's username.
+    pub username: String,
+}
+
+/// The ID and token of a webhook, which are all that is needed to execute
+/// it.
+///
+/// These are what should be stored after the authorization of
+/// [`Scope::WebhookIncoming`].
+///
+/// # Examples
+///
+/// Persist the credentials as JSON, and load them back later:
+///
+/// ```rust
+/// extern crate serde_json;
+/// extern crate serenity_oauth;
+///
+/// use serenity_oauth::model::WebhookCredentials;
+///
+/// # fn main() {
+/// let webhook = WebhookCredentials::new(381880193700069377, "webhook token");
+///
+/// let json = serde_json::to_string(&webhook).unwrap();
+/// assert_eq!(json, r#"{"id":"381880193700069377","token":"webhook token"}"#);
+///
+/// let loaded: WebhookCredentials = serde_json::from_str(&json).unwrap();
+/// assert_eq!(loaded, webhook);
+/// # }
+/// ```
+///
+/// [`Scope::WebhookIncoming`]: ../enum.Scope.html#variant.WebhookIncoming
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct WebhookCredentials {
+    /// The ID of the webhook.
+    #[serde(with = "snowflake")]
+    pub id: u64,
+    /// The secure token of the webhook.
+    pub token: String,
+}
+
+impl WebhookCredentials {
+    /// Creates credentials from a webhook's ID and token.
+    pub fn new<S: Into<String>>(id: u64, token: S) -> Self {
+        Self {
+            token: token.into(),
+            id,
+        }
+    }
+}
+
+impl<'a> From<&'a Webhook> for WebhookCredentials {
+    fn from(webhook: &'a Webhook) -> Self {
+        Self::new(webhook.id.0, webhook.token.clone())
+    }
+}
+
+/// A parsed webhook event.
+///
+/// Refer to [`WebhookEventBody::parse`] for how events are parsed.
+///
+/// [`WebhookEventBody::parse`]: struct.WebhookEventBody.html#method.parse
+#[derive(Clone, Debug)]
+pub enum WebhookEvent {
+    /// A user authorized your application.
+    ApplicationAuthorized(ApplicationAuthorizedEvent),
+    /// A user deauthorized your application.
+    ApplicationDeauthorized(ApplicationDeauthorizedEvent),
+    /// An event that does not have a matching enum variant, along with its
+    /// raw data.
+    Other(WebhookEventType, Value),
+}
+
+/// The event contained in a [`WebhookEventPayload`].
+///
+/// [`WebhookEventPayload`]: struct.WebhookEventPayload.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WebhookEventBody {
+    /// The raw data of the event.
+    #[serde(default)]
+    pub data: Value,
+    /// The type of the event.
+    #[serde(rename = "type")]
+    pub kind: WebhookEventType,
+    /// When the event occurred.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl WebhookEventBody {
+    /// Parses the [`data`] of the event according to its [`kind`].
+    ///
+    /// [`data`]: #structfield.data
+    /// [`kind`]: #structfield.kind
+    pub fn parse(&self) -> Result<WebhookEvent, JsonError> {
+        let data = self.data.clone();
+
+        Ok(match self.kind {
+            WebhookEventType::ApplicationAuthorized => {
+                WebhookEvent::ApplicationAuthorized(
+                    serde_json::from_value(data)?,
+                )
+            },
+            WebhookEventType::ApplicationDeauthorized => {
+                WebhookEvent::ApplicationDeauthorized(
+                    serde_json::from_value(data)?,
+                )
+            },
+            ref other => WebhookEvent::Other(other.clone(), data),
+        })
+    }
+}
+
+/// A payload sent to your application's webhook events URL.
+///
+/// # Examples
+///
+/// Parse the payload of a user deauthorizing your application:
+///
+/// ```rust
+/// extern crate serde_json;
+/// extern crate serenity_oauth;
+///
+/// use serenity_oauth::model::{WebhookEvent, WebhookEventPayload};
+///
+/// # fn main() {
+/// let payload: WebhookEventPayload = serde_json::from_str(r#"{
+///     "version": 1,
+///     "application_id": "249608697955745802",
+///     "type": 1,
+///     "event": {
+///         "type": "APPLICATION_DEAUTHORIZED",
+///         "timestamp": "2024-10-18T14:42:53.064834+00:00",
+///         "data": {
+///             "user": {
+///                 "id": "114941315417899012",
+///                 "username": "nelly",
+///                 "discriminator": "0"
+///             }
+///         }
+///     }
+/// }"#).unwrap();
+///
+/// assert!(!payload.is_ping());
+///
+/// match payload.event.unwrap().parse().unwrap() {
+///     WebhookEvent::ApplicationDeauthorized(event) => {
+///         assert_eq!(event.user.id, 114941315417899012);
+///     },
+///     _ => unreachable!(),
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WebhookEventPayload {
+    /// The ID of your application.
+    #[serde(with = "snowflake")]
+    pub application_id: u64,
+    /// The event, if the payload is of the [`WebhookType::Event`] type.
+    ///
+    /// [`WebhookType::Event`]: enum.WebhookType.html#variant.Event
+    #[serde(default)]
+    pub event: Option<WebhookEventBody>,
+    /// The type of the payload.
+    #[serde(rename = "type")]
+    pub kind: WebhookType,
+    /// The version of the payload's format.
+    pub version: u8,
+}
+
+impl WebhookEventPayload {
+    /// Whether the payload is a ping sent by Discord to check that your
+    /// webhook events URL is reachable.
+    pub fn is_ping(&self) -> bool {
+        self.kind == WebhookType::Ping
+    }
+}
+
+/// The type of an event sent to your application's webhook events URL.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum WebhookEventType {
+    /// A user authorized your application.
+    ApplicationAuthorized,
+    /// A user deauthorized your application.
+    ApplicationDeauthorized,
+    /// An entitlement was created.
+    EntitlementCreate,
+    /// A user enrolled in a quest.
+    QuestUserEnrollment,
+    /// An event type that does not have a matching enum variant.
+    Other(String),
+}
+
+enum_string!(WebhookEventType {
+    ApplicationAuthorized = "APPLICATION_AUTHORIZED",
+    ApplicationDeauthorized = "APPLICATION_DEAUTHORIZED",
+    EntitlementCreate = "ENTITLEMENT_CREATE",
+    QuestUserEnrollment = "QUEST_USER_ENROLLMENT",
+});
+
+/// A message sent by a webhook.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WebhookMessage {
+    /// The ID of the channel the message was sent in.
+    #[serde(with = "snowflake")]
+    pub channel_id: u64,
+    /// The content of the message.
+    #[serde(default)]
+    pub content: String,
+    /// When the message was last edited, if it has been.
+    #[serde(default)]
+    pub edited_timestamp: Option<DateTime<Utc>>,
+    /// The embeds of the message, as embed objects.
+    #[serde(default)]
+    pub embeds: Vec<Value>,
+    /// The ID of the message.
+    #[serde(with = "snowflake")]
+    pub id: u64,
+    /// When the message was sent.
+    pub timestamp: DateTime<Utc>,
+    /// The ID of the webhook that sent the message.
+    #[serde(default, with = "option_snowflake")]
+    pub webhook_id: Option<u64>,
+}
+
 /// The response data from a successful trading of a code for an access token
 /// after authorization of [`Scope::WebhookIncoming`].
 ///
@@ -194,3 +1780,184 @@ pub struct WebhookTokenResponse {
     /// Information about the webhook created.
     pub webhook: Webhook,
 }
+
+impl WebhookTokenResponse {
+    /// The ID and token of the created webhook, which should be stored to
+    /// execute it later.
+    pub fn credentials(&self) -> WebhookCredentials {
+        WebhookCredentials::from(&self.webhook)
+    }
+}
+
+/// The type of a [`WebhookEventPayload`].
+///
+/// [`WebhookEventPayload`]: struct.WebhookEventPayload.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WebhookType {
+    /// A ping sent to check that your webhook events URL is reachable.
+    Ping,
+    /// A payload containing an event.
+    Event,
+    /// A payload type that does not have a matching enum variant.
+    Other(u8),
+}
+
+enum_number!(WebhookType {
+    Ping = 0,
+    Event = 1,
+});
+
+/// (De)serialization of snowflake IDs, which Discord's API represents as
+/// strings.
+mod snowflake {
+    use serde::de::{Deserializer, Error, Unexpected, Visitor};
+    use serde::ser::Serializer;
+    use std::fmt::{Formatter, Result as FmtResult};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+        where D: Deserializer<'de> {
+        deserializer.deserialize_any(SnowflakeVisitor)
+    }
+
+    pub fn serialize<S>(id: &u64, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer {
+        serializer.collect_str(id)
+    }
+
+    struct SnowflakeVisitor;
+
+    impl<'de> Visitor<'de> for SnowflakeVisitor {
+        type Value = u64;
+
+        fn expecting(&self, f: &mut Formatter) -> FmtResult {
+            f.write_str("a snowflake ID")
+        }
+
+        fn visit_u64<E: Error>(self, value: u64) -> Result<u64, E> {
+            Ok(value)
+        }
+
+        fn visit_str<E: Error>(self, value: &str) -> Result<u64, E> {
+            value.parse().map_err(|_| {
+                Error::invalid_value(Unexpected::Str(value), &self)
+            })
+        }
+    }
+}
+
+/// (De)serialization of permission bitfields, which Discord's API represents as
+/// strings.
+mod permissions {
+    use serde::de::Deserializer;
+    use serde::ser::Serializer;
+    use serenity_model::Permissions;
+    use super::snowflake;
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Permissions, D::Error>
+        where D: Deserializer<'de> {
+        snowflake::deserialize(deserializer)
+            .map(Permissions::from_bits_truncate)
+    }
+
+    pub fn serialize<S>(permissions: &Permissions, serializer: S)
+        -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.collect_str(&permissions.bits())
+    }
+}
+
+/// (De)serialization of lists of snowflake IDs.
+mod snowflakes {
+    use serde::de::{Deserialize, Deserializer};
+    use serde::ser::{SerializeSeq, Serializer};
+
+    #[derive(Deserialize)]
+    struct Snowflake(#[serde(with = "super::snowflake")] u64);
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
+        where D: Deserializer<'de> {
+        Vec::<Snowflake>::deserialize(deserializer).map(|ids| {
+            ids.into_iter().map(|id| id.0).collect()
+        })
+    }
+
+    pub fn serialize<S>(ids: &[u64], serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer {
+        let mut seq = serializer.serialize_seq(Some(ids.len()))?;
+
+        for id in ids {
+            seq.serialize_element(&id.to_string())?;
+        }
+
+        seq.end()
+    }
+}
+
+/// (De)serialization of optional snowflake IDs.
+mod option_snowflake {
+    use serde::de::{Deserialize, Deserializer};
+    use serde::ser::Serializer;
+
+    #[derive(Deserialize)]
+    struct Snowflake(#[serde(with = "super::snowflake")] u64);
+
+    pub fn deserialize<'de, D>(deserializer: D)
+        -> Result<Option<u64>, D::Error> where D: Deserializer<'de> {
+        Option::<Snowflake>::deserialize(deserializer).map(|id| {
+            id.map(|id| id.0)
+        })
+    }
+
+    pub fn serialize<S>(id: &Option<u64>, serializer: S)
+        -> Result<S::Ok, S::Error> where S: Serializer {
+        match *id {
+            Some(id) => serializer.collect_str(&id),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Deserialization of optional permissions.
+mod option_permissions {
+    use serde::de::{Deserialize, Deserializer};
+    use serenity_model::Permissions;
+
+    #[derive(Deserialize)]
+    struct PermissionsBits(#[serde(with = "super::permissions")] Permissions);
+
+    pub fn deserialize<'de, D>(deserializer: D)
+        -> Result<Option<Permissions>, D::Error> where D: Deserializer<'de> {
+        Option::<PermissionsBits>::deserialize(deserializer).map(|bits| {
+            bits.map(|bits| bits.0)
+        })
+    }
+}
+
+/// (De)serialization of values that are either a single string or a list of
+/// strings.
+mod string_or_vec {
+    use serde::de::{Deserialize, Deserializer};
+    use serde::ser::Serializer;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrVec {
+        String(String),
+        Vec(Vec<String>),
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D)
+        -> Result<Vec<String>, D::Error> where D: Deserializer<'de> {
+        StringOrVec::deserialize(deserializer).map(|value| match value {
+            StringOrVec::String(value) => vec![value],
+            StringOrVec::Vec(values) => values,
+        })
+    }
+
+    pub fn serialize<S>(values: &[String], serializer: S)
+        -> Result<S::Ok, S::Error> where S: Serializer {
+        match values.len() {
+            1 => serializer.serialize_str(&values[0]),
+            _ => serializer.collect_seq(values),
+        }
+    }
+}
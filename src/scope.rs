@@ -1,4 +1,7 @@
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fmt::Write;
 
 /// A Discord OAuth2 scope that can be granted.
 ///
@@ -30,6 +33,9 @@ pub enum Scope {
     /// Allows the `/invites/{code}` API endpoint to be used for joining users
     /// to a guild.
     GuildsJoin,
+    /// Allows the `/users/@me/guilds/{guild.id}/member` API endpoint to return
+    /// the user's member information in a guild.
+    GuildsMembersRead,
     /// Allows your application to join users to a group DM.
     GdmJoin,
     /// For local RPC server API access, this allows you to read messages from
@@ -38,6 +44,9 @@ pub enum Scope {
     /// This is otherwise restricted to channels/guilds your application
     /// creates.
     MessagesRead,
+    /// For OpenID Connect, this adds an ID token to the token response,
+    /// identifying the user.
+    OpenId,
     /// For local RPC server access, this allows you to control a user's local
     /// Discord client.
     Rpc,
@@ -47,6 +56,9 @@ pub enum Scope {
     /// For local RPC server API access, this allows you to receive
     /// notifications pushed out to the user.
     RpcNotificationsRead,
+    /// Allows your application to update the user's role connection, used by
+    /// Linked Roles.
+    RoleConnectionsWrite,
     /// This generates a webhook that is returned in the OAuth token response
     /// for authorization code grants.
     WebhookIncoming,
@@ -54,6 +66,74 @@ pub enum Scope {
     Other(String),
 }
 
+impl Scope {
+    /// Parses a space-separated list of scopes, such as the `scope` field of an
+    /// access token response.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_oauth::Scope;
+    ///
+    /// let scopes = Scope::parse_list("identify guilds.join");
+    ///
+    /// assert_eq!(scopes, vec![Scope::Identify, Scope::GuildsJoin]);
+    /// ```
+    pub fn parse_list(scopes: &str) -> Vec<Scope> {
+        scopes.split_whitespace().map(Scope::from).collect()
+    }
+
+    /// Joins scopes into a space-separated list, as used in request bodies.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_oauth::Scope;
+    ///
+    /// let scopes = Scope::join_list(&[Scope::Identify, Scope::Email]);
+    ///
+    /// assert_eq!(scopes, "identify email");
+    /// ```
+    pub fn join_list(scopes: &[Scope]) -> String {
+        let mut joined = String::new();
+
+        for (i, scope) in scopes.iter().enumerate() {
+            if i > 0 {
+                joined.push(' ');
+            }
+
+            let _ = write!(joined, "{}", scope);
+        }
+
+        joined
+    }
+}
+
+impl<'a> From<&'a str> for Scope {
+    fn from(scope: &'a str) -> Self {
+        use self::Scope::*;
+
+        match scope {
+            "bot" => Bot,
+            "connections" => Connections,
+            "email" => Email,
+            "identify" => Identify,
+            "guilds" => Guilds,
+            "guilds.join" => GuildsJoin,
+            "guilds.members.read" => GuildsMembersRead,
+            "gdm.join" => GdmJoin,
+            "messages.read" => MessagesRead,
+            "openid" => OpenId,
+            "rpc" => Rpc,
+            "rpc.api" => RpcApi,
+            "rpc.notifications.read" => RpcNotificationsRead,
+            "role_connections.write" => RoleConnectionsWrite,
+            "webhook.incoming" => WebhookIncoming,
+            other => Other(other.to_owned()),
+        }
+    }
+}
+
 impl Display for Scope {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         use self::Scope::*;
@@ -65,13 +145,30 @@ impl Display for Scope {
             Identify => "identify",
             Guilds => "guilds",
             GuildsJoin => "guilds.join",
+            GuildsMembersRead => "guilds.members.read",
             GdmJoin => "gdm.join",
             MessagesRead => "messages.read",
+            OpenId => "openid",
             Rpc => "rpc",
             RpcApi => "rpc.api",
             RpcNotificationsRead => "rpc.notifications.read",
+            RoleConnectionsWrite => "role_connections.write",
             WebhookIncoming => "webhook.incoming",
             Other(ref inner) => inner,
         })
     }
 }
+
+impl<'de> Deserialize<'de> for Scope {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de> {
+        String::deserialize(deserializer).map(|scope| Scope::from(&*scope))
+    }
+}
+
+impl Serialize for Scope {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer {
+        serializer.collect_str(self)
+    }
+}
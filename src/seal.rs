@@ -0,0 +1,369 @@
+//! Sealing of session cookies for stateless web servers.
+//!
+//! A [`SealedSession`] holds the ID of a logged in user, the scopes they
+//! granted, and the key under which their grant is kept in a [`TokenStore`].
+//! The [`SessionSealer`] encrypts and authenticates it into a cookie value, so
+//! that neither the session can be tampered with nor the access token reach
+//! the browser. The sealer can also seal any other value kept in a cookie.
+//!
+//! Sessions are sealed with ChaCha20-Poly1305. The sealing key can be rotated
+//! while keeping sessions sealed with previous keys valid.
+//!
+//! The [web framework integrations] keep the logged in user this way.
+//!
+//! [web framework integrations]: ../integrations/index.html
+//! [`SealedSession`]: struct.SealedSession.html
+//! [`SessionSealer`]: struct.SessionSealer.html
+//! [`TokenStore`]: ../store/trait.TokenStore.html
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use model::CurrentUser;
+use ring::aead::{
+    Aad,
+    LessSafeKey,
+    Nonce,
+    UnboundKey,
+    CHACHA20_POLY1305,
+    NONCE_LEN,
+};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+use serde_json;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use super::{Error, Result, Scope};
+
+/// The purpose sessions are sealed for, so that no other sealed value can be
+/// opened as a session.
+const SESSION_PURPOSE: &str = "session";
+
+/// The reason a session could not be sealed or opened.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SealError {
+    /// The session has expired.
+    Expired,
+    /// The sealed value is malformed, was tampered with, or was not sealed
+    /// with any of the keys of the sealer.
+    Invalid,
+    /// A sealing key is not 32 bytes encoded as URL-safe base64.
+    InvalidKey,
+    /// The system's random number generator failed to generate a nonce.
+    Rng,
+}
+
+impl Display for SealError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self.description())
+    }
+}
+
+impl StdError for SealError {
+    fn description(&self) -> &str {
+        match *self {
+            SealError::Expired => "Sealed session expired",
+            SealError::Invalid => "Invalid sealed session",
+            SealError::InvalidKey => "Invalid sealing key",
+            SealError::Rng => "Error generating sealing nonce",
+        }
+    }
+}
+
+/// A logged in user, as kept in a sealed session cookie.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SealedSession {
+    /// The time at which the session expires.
+    pub expires_at: DateTime<Utc>,
+    /// The scopes the user granted.
+    pub scopes: Vec<Scope>,
+    /// The key under which the user's grant is kept in a [`TokenStore`].
+    ///
+    /// [`TokenStore`]: ../store/trait.TokenStore.html
+    pub token_key: String,
+    /// The logged in user, if kept in the session.
+    #[serde(default)]
+    pub user: Option<CurrentUser>,
+    /// The ID of the user.
+    pub user_id: u64,
+}
+
+impl SealedSession {
+    /// Creates a session that expires after the given lifetime.
+    pub fn new<S>(
+        user_id: u64,
+        scopes: Vec<Scope>,
+        token_key: S,
+        lifetime: Duration,
+    ) -> Self where S: Into<String> {
+        Self {
+            expires_at: Utc::now() + lifetime,
+            scopes,
+            token_key: token_key.into(),
+            user: None,
+            user_id,
+        }
+    }
+
+    /// Keeps the given user in the session, such as to greet them without
+    /// making a request to Discord.
+    pub fn with_user(mut self, user: CurrentUser) -> Self {
+        self.user = Some(user);
+
+        self
+    }
+
+    /// Whether the user granted the given scope.
+    pub fn has_scope(&self, scope: &Scope) -> bool {
+        self.scopes.contains(scope)
+    }
+
+    /// Whether the session has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
+
+/// A 256-bit key to seal sessions with.
+#[derive(Clone)]
+pub struct SealingKey([u8; 32]);
+
+impl SealingKey {
+    /// Creates a key from its bytes.
+    pub fn new(bytes: [u8; 32]) -> Self {
+        SealingKey(bytes)
+    }
+
+    /// Generates a random key.
+    pub fn generate() -> Self {
+        let mut bytes = [0; 32];
+
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .expect("Error generating sealing key");
+
+        SealingKey(bytes)
+    }
+
+    /// Decodes a key from URL-safe base64 without padding, as produced by
+    /// [`to_base64`].
+    ///
+    /// Returns [`SealError::InvalidKey`] if the key is not 32 bytes.
+    ///
+    /// [`SealError::InvalidKey`]: enum.SealError.html#variant.InvalidKey
+    /// [`to_base64`]: #method.to_base64
+    pub fn from_base64(encoded: &str) -> Result<Self> {
+        let decoded = URL_SAFE_NO_PAD.decode(encoded.trim())
+            .map_err(|_| Error::Seal(SealError::InvalidKey))?;
+
+        if decoded.len() != 32 {
+            return Err(Error::Seal(SealError::InvalidKey));
+        }
+
+        let mut bytes = [0; 32];
+        bytes.copy_from_slice(&decoded);
+
+        Ok(SealingKey(bytes))
+    }
+
+    /// Encodes the key as URL-safe base64 without padding, such as to keep it
+    /// in your configuration.
+    pub fn to_base64(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.0)
+    }
+
+    fn aead(&self) -> LessSafeKey {
+        let key = UnboundKey::new(&CHACHA20_POLY1305, &self.0)
+            .expect("Sealing keys are 32 bytes");
+
+        LessSafeKey::new(key)
+    }
+}
+
+/// A sealer of [`SealedSession`]s into cookie values.
+///
+/// Sessions are always sealed with the current key, and opened with the
+/// current key or any of the previous keys. To rotate the key, [`rotate`] to a
+/// new key, and drop the oldest previous keys once the sessions sealed with
+/// them have expired.
+///
+/// # Examples
+///
+/// Seal a session, and open it again after rotating the key:
+///
+/// ```rust
+/// extern crate chrono;
+/// extern crate serenity_oauth;
+///
+/// use chrono::Duration;
+/// use serenity_oauth::seal::{SealedSession, SealingKey, SessionSealer};
+/// use serenity_oauth::Scope;
+///
+/// # fn main() {
+/// let mut sealer = SessionSealer::new(SealingKey::generate());
+///
+/// let session = SealedSession::new(
+///     114941315417899012,
+///     vec![Scope::Identify, Scope::Guilds],
+///     "114941315417899012",
+///     Duration::days(7),
+/// );
+/// let sealed = sealer.seal(&session).unwrap();
+///
+/// sealer.rotate(SealingKey::generate());
+///
+/// assert_eq!(sealer.open(&sealed).unwrap(), session);
+/// assert!(!sealer.is_current(&sealed));
+///
+/// // Sessions can not be opened once tampered with.
+/// let first = if sealed.starts_with('A') { "B" } else { "A" };
+/// let tampered = format!("{}{}", first, &sealed[1..]);
+/// assert!(sealer.open(&tampered).is_err());
+///
+/// // Nor can values sealed for another purpose.
+/// let state = sealer.seal_value("state", &session).unwrap();
+/// assert!(sealer.open(&state).is_err());
+/// assert!(sealer.open_value::<SealedSession>("state", &state).is_ok());
+/// # }
+/// ```
+///
+/// [`SealedSession`]: struct.SealedSession.html
+/// [`rotate`]: #method.rotate
+#[derive(Clone)]
+pub struct SessionSealer {
+    keys: Vec<SealingKey>,
+}
+
+impl SessionSealer {
+    /// Creates a sealer with the given current key.
+    pub fn new(key: SealingKey) -> Self {
+        Self {
+            keys: vec![key],
+        }
+    }
+
+    /// Accepts sessions sealed with the given previous key.
+    pub fn with_previous_key(mut self, key: SealingKey) -> Self {
+        self.keys.push(key);
+
+        self
+    }
+
+    /// Makes the given key the current key, keeping the current key as the
+    /// most recent previous key.
+    pub fn rotate(&mut self, key: SealingKey) {
+        self.keys.insert(0, key);
+    }
+
+    /// Drops all but the given number of most recent previous keys.
+    pub fn retain_previous_keys(&mut self, count: usize) {
+        self.keys.truncate(count + 1);
+    }
+
+    /// Seals a session with the current key.
+    pub fn seal(&self, session: &SealedSession) -> Result<String> {
+        self.seal_value(SESSION_PURPOSE, session)
+    }
+
+    /// Seals any serializable value with the current key, for the given
+    /// purpose.
+    ///
+    /// The purpose, such as the name of the cookie the value is kept in, is
+    /// authenticated along with the value. The value can only be opened for
+    /// the same purpose, so that a value sealed for one cookie can not be
+    /// passed off as another. Unlike sessions, the value carries no expiry.
+    pub fn seal_value<T>(&self, purpose: &str, value: &T) -> Result<String>
+        where T: Serialize {
+        let mut nonce = [0; NONCE_LEN];
+
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| Error::Seal(SealError::Rng))?;
+
+        let mut data = serde_json::to_vec(value)?;
+
+        self.keys[0].aead()
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(purpose),
+                &mut data,
+            )
+            .map_err(|_| Error::Seal(SealError::Invalid))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&data);
+
+        Ok(URL_SAFE_NO_PAD.encode(sealed))
+    }
+
+    /// Opens a sealed session, checking that it has not expired.
+    ///
+    /// Returns [`SealError::Invalid`] if the session could not be opened with
+    /// any of the keys, and [`SealError::Expired`] if it has expired.
+    ///
+    /// [`SealError::Expired`]: enum.SealError.html#variant.Expired
+    /// [`SealError::Invalid`]: enum.SealError.html#variant.Invalid
+    pub fn open(&self, sealed: &str) -> Result<SealedSession> {
+        self.open_at(sealed, Utc::now())
+    }
+
+    /// Opens a sealed session, checking that it has not expired by the given
+    /// time.
+    ///
+    /// Refer to [`open`] for more information.
+    ///
+    /// [`open`]: #method.open
+    pub fn open_at(&self, sealed: &str, now: DateTime<Utc>)
+        -> Result<SealedSession> {
+        let session: SealedSession = self.open_value(SESSION_PURPOSE, sealed)?;
+
+        if session.expires_at <= now {
+            return Err(Error::Seal(SealError::Expired));
+        }
+
+        Ok(session)
+    }
+
+    /// Opens a value sealed with [`seal_value`] for the given purpose.
+    ///
+    /// Returns [`SealError::Invalid`] if the value could not be opened with
+    /// any of the keys, or was sealed for another purpose.
+    ///
+    /// [`SealError::Invalid`]: enum.SealError.html#variant.Invalid
+    /// [`seal_value`]: #method.seal_value
+    pub fn open_value<T>(&self, purpose: &str, sealed: &str) -> Result<T>
+        where T: DeserializeOwned {
+        self.keys.iter()
+            .filter_map(|key| open_with(key, purpose, sealed))
+            .next()
+            .and_then(|plaintext| serde_json::from_slice(&plaintext).ok())
+            .ok_or(Error::Seal(SealError::Invalid))
+    }
+
+    /// Whether the session was sealed with the current key.
+    ///
+    /// Sessions sealed with a previous key should be sealed again, so that
+    /// they remain valid once the previous key is dropped.
+    pub fn is_current(&self, sealed: &str) -> bool {
+        open_with(&self.keys[0], SESSION_PURPOSE, sealed).is_some()
+    }
+}
+
+fn open_with(key: &SealingKey, purpose: &str, sealed: &str)
+    -> Option<Vec<u8>> {
+    let mut data = URL_SAFE_NO_PAD.decode(sealed.trim()).ok()?;
+
+    if data.len() < NONCE_LEN {
+        return None;
+    }
+
+    let mut ciphertext = data.split_off(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(&data).ok()?;
+
+    let plaintext = key.aead()
+        .open_in_place(nonce, Aad::from(purpose), &mut ciphertext)
+        .ok()?;
+
+    Some(plaintext.to_vec())
+}
@@ -0,0 +1,154 @@
+//! Storage for users' OAuth2 grants.
+//!
+//! The [`TokenStore`] trait can be implemented on top of any storage backend,
+//! such as a database, to keep track of users' access and refresh tokens. A
+//! simple in-memory implementation is provided as [`MemoryTokenStore`].
+//!
+//! [`MemoryTokenStore`]: struct.MemoryTokenStore.html
+//! [`TokenStore`]: trait.TokenStore.html
+
+use model::AccessTokenResponse;
+use std::collections::HashMap;
+use std::sync::{
+    Arc,
+    PoisonError,
+    RwLock,
+    RwLockReadGuard,
+    RwLockWriteGuard,
+};
+use std::time::{Duration, SystemTime};
+
+/// A user's grant as kept in a [`TokenStore`].
+///
+/// [`TokenStore`]: trait.TokenStore.html
+#[derive(Clone, Debug)]
+pub struct StoredToken {
+    /// The user's access token.
+    pub access_token: String,
+    /// The time at which the access token expires.
+    pub expires_at: SystemTime,
+    /// The refresh token to use when the access token expires.
+    pub refresh_token: String,
+    /// Whether the grant has been revoked, such as by the user deauthorizing
+    /// the application.
+    ///
+    /// A revoked grant can not be refreshed.
+    pub revoked: bool,
+    /// The scope that is granted.
+    pub scope: String,
+    /// The type of token.
+    pub token_type: String,
+}
+
+impl StoredToken {
+    /// Whether the access token has expired, and needs to be refreshed before
+    /// it can be used.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= SystemTime::now()
+    }
+}
+
+impl From<AccessTokenResponse> for StoredToken {
+    fn from(response: AccessTokenResponse) -> Self {
+        Self {
+            access_token: response.access_token,
+            expires_at: SystemTime::now()
+                + Duration::from_secs(response.expires_in),
+            refresh_token: response.refresh_token,
+            revoked: false,
+            scope: response.scope,
+            token_type: response.token_type,
+        }
+    }
+}
+
+/// A trait for storing users' grants, keyed by an identifier of your choosing,
+/// such as the user's ID.
+pub trait TokenStore {
+    /// Retrieves the grant stored under the given key, if there is one.
+    fn get(&self, key: &str) -> Option<StoredToken>;
+
+    /// Stores a grant under the given key, replacing any previous grant.
+    fn insert(&self, key: &str, token: StoredToken);
+
+    /// Marks the grant stored under the given key as revoked.
+    fn mark_revoked(&self, key: &str);
+}
+
+impl<T> TokenStore for Arc<T> where T: TokenStore + ?Sized {
+    fn get(&self, key: &str) -> Option<StoredToken> {
+        (**self).get(key)
+    }
+
+    fn insert(&self, key: &str, token: StoredToken) {
+        (**self).insert(key, token)
+    }
+
+    fn mark_revoked(&self, key: &str) {
+        (**self).mark_revoked(key)
+    }
+}
+
+/// A [`TokenStore`] that keeps grants in memory.
+///
+/// # Examples
+///
+/// Insert a grant and then mark it as revoked:
+///
+/// ```rust
+/// use serenity_oauth::store::{MemoryTokenStore, StoredToken, TokenStore};
+/// use std::time::SystemTime;
+///
+/// let store = MemoryTokenStore::new();
+/// store.insert("user", StoredToken {
+///     access_token: "access".to_owned(),
+///     expires_at: SystemTime::now(),
+///     refresh_token: "refresh".to_owned(),
+///     revoked: false,
+///     scope: "identify".to_owned(),
+///     token_type: "Bearer".to_owned(),
+/// });
+///
+/// store.mark_revoked("user");
+///
+/// assert!(store.get("user").unwrap().revoked);
+/// ```
+///
+/// [`TokenStore`]: trait.TokenStore.html
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    tokens: RwLock<HashMap<String, StoredToken>>,
+}
+
+impl MemoryTokenStore {
+    /// Creates a new, empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn get(&self, key: &str) -> Option<StoredToken> {
+        read(&self.tokens).get(key).cloned()
+    }
+
+    fn insert(&self, key: &str, token: StoredToken) {
+        write(&self.tokens).insert(key.to_owned(), token);
+    }
+
+    fn mark_revoked(&self, key: &str) {
+        if let Some(token) = write(&self.tokens).get_mut(key) {
+            token.revoked = true;
+        }
+    }
+}
+
+// A panic while a lock is held can not leave the store inconsistent, as tokens
+// are only ever replaced whole, so a poisoned lock is recovered from.
+fn read<'a, T>(lock: &'a RwLock<T>) -> RwLockReadGuard<'a, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write<'a, T>(lock: &'a RwLock<T>) -> RwLockWriteGuard<'a, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
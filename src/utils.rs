@@ -5,9 +5,8 @@
 
 pub use serenity_model::Permissions;
 
-use constants::BASE_AUTHORIZE_URI;
 use percent_encoding;
-use super::Scope;
+use super::{Endpoints, Scope};
 use std::fmt::Write;
 
 /// Creates a URL for a simple bot authorization flow.
@@ -39,9 +38,49 @@ use std::fmt::Write;
 /// ```
 pub fn bot_authorization_url(client_id: u64, permissions: Permissions)
     -> String {
+    bot_authorization_url_with_endpoints(
+        &Endpoints::default(),
+        client_id,
+        permissions,
+    )
+}
+
+/// Creates a URL for a simple bot authorization flow, using the authorization
+/// endpoint of the given [`Endpoints`].
+///
+/// Refer to [`bot_authorization_url`] for more information.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate serenity_model;
+/// extern crate serenity_oauth;
+///
+/// # fn main() {
+/// use serenity_model::Permissions;
+/// use serenity_oauth::Endpoints;
+///
+/// let url = serenity_oauth::utils::bot_authorization_url_with_endpoints(
+///     &Endpoints::discord(),
+///     249608697955745802,
+///     Permissions::SEND_MESSAGES,
+/// );
+///
+/// let expected = "https://discord.com/oauth2/authorize?client_id=249608697955745802&scope=bot&permissions=2048";
+/// assert_eq!(url, expected);
+/// # }
+/// ```
+///
+/// [`Endpoints`]: ../struct.Endpoints.html
+/// [`bot_authorization_url`]: fn.bot_authorization_url.html
+pub fn bot_authorization_url_with_endpoints(
+    endpoints: &Endpoints,
+    client_id: u64,
+    permissions: Permissions,
+) -> String {
     format!(
         "{}?client_id={}&scope=bot&permissions={}",
-        BASE_AUTHORIZE_URI,
+        endpoints.authorize_url(),
         client_id,
         permissions.bits(),
     )
@@ -99,7 +138,30 @@ pub fn authorization_code_grant_url(
     state: Option<&str>,
     redirect_uri: &str,
 ) -> String {
-    let mut base = String::from(BASE_AUTHORIZE_URI);
+    authorization_code_grant_url_with_endpoints(
+        &Endpoints::default(),
+        client_id,
+        scopes,
+        state,
+        redirect_uri,
+    )
+}
+
+/// Creates a URL for an authorization code grant, using the authorization
+/// endpoint of the given [`Endpoints`].
+///
+/// Refer to [`authorization_code_grant_url`] for more information.
+///
+/// [`Endpoints`]: ../struct.Endpoints.html
+/// [`authorization_code_grant_url`]: fn.authorization_code_grant_url.html
+pub fn authorization_code_grant_url_with_endpoints(
+    endpoints: &Endpoints,
+    client_id: u64,
+    scopes: &[Scope],
+    state: Option<&str>,
+    redirect_uri: &str,
+) -> String {
+    let mut base = endpoints.authorize_url();
     let uri = percent_encoding::percent_encode(
         redirect_uri.as_bytes(),
         percent_encoding::USERINFO_ENCODE_SET,
@@ -128,3 +190,56 @@ pub fn authorization_code_grant_url(
 
     base
 }
+
+/// Creates a URL for an OpenID Connect authorization code grant, including a
+/// nonce that the returned ID token must contain.
+///
+/// The [`Scope::OpenId`] scope should be one of the given scopes. Refer to
+/// [`authorization_code_grant_url`] for more information.
+///
+/// **Note**: Please randomly generate the nonce for each request using a crate
+/// like `rand`, and keep it to validate the ID token with.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_oauth::{Endpoints, Scope};
+///
+/// let url = serenity_oauth::utils::authorization_code_grant_url_with_nonce(
+///     &Endpoints::default(),
+///     249608697955745802,
+///     &[Scope::OpenId, Scope::Identify],
+///     Some("15773059ghq9183habn"),
+///     "n-0S6_WzA2Mj",
+///     "https://myapplication.website",
+/// );
+///
+/// assert!(url.ends_with("&state=15773059ghq9183habn&nonce=n-0S6_WzA2Mj"));
+/// ```
+///
+/// [`Scope::OpenId`]: ../enum.Scope.html#variant.OpenId
+/// [`authorization_code_grant_url`]: fn.authorization_code_grant_url.html
+pub fn authorization_code_grant_url_with_nonce(
+    endpoints: &Endpoints,
+    client_id: u64,
+    scopes: &[Scope],
+    state: Option<&str>,
+    nonce: &str,
+    redirect_uri: &str,
+) -> String {
+    let mut base = authorization_code_grant_url_with_endpoints(
+        endpoints,
+        client_id,
+        scopes,
+        state,
+        redirect_uri,
+    );
+    let nonce = percent_encoding::percent_encode(
+        nonce.as_bytes(),
+        percent_encoding::USERINFO_ENCODE_SET,
+    );
+
+    let _ = write!(base, "&nonce={}", nonce);
+
+    base
+}